@@ -1,11 +1,9 @@
-use std::path::Path;
 use std::sync::Arc;
 
 use clx::progress::ProgressJob;
 use log::info;
 
 use crate::error::{Error, Result};
-use crate::github::GitHubClient;
 use crate::links;
 use crate::llm::{LlmClient, StopReason, ToolDefinition, ToolResult, Usage};
 use crate::output::{self, ParsedOutput};
@@ -126,8 +124,7 @@ pub struct AgentContext<'a> {
     pub system: &'a str,
     pub user_message: &'a str,
     pub tool_defs: Vec<ToolDefinition>,
-    pub repo_root: &'a Path,
-    pub github: Option<&'a GitHubClient>,
+    pub tools: tools::ToolContext<'a>,
     pub verify_links: bool,
     pub require_release_notes: bool,
     pub require_changelog: bool,
@@ -140,8 +137,7 @@ pub async fn run(ctx: AgentContext<'_>) -> Result<ParsedOutput> {
         system,
         user_message,
         tool_defs,
-        repo_root,
-        github,
+        tools: tool_ctx,
         verify_links,
         require_release_notes,
         require_changelog,
@@ -332,7 +328,7 @@ pub async fn run(ctx: AgentContext<'_>) -> Result<ParsedOutput> {
             .iter()
             .map(|&i| {
                 let tc = &response.tool_calls[i];
-                tools::dispatch(&tc.name, &tc.input, repo_root, github)
+                tools::dispatch(&tc.name, &tc.input, &tool_ctx)
             })
             .collect();
         let dispatch_outcomes = futures_util::future::join_all(futures).await;
@@ -435,10 +431,22 @@ fn tool_detail(name: &str, input: &serde_json::Value) -> String {
             let number = &input["number"];
             format!("get_pr(#{number})")
         }
+        "get_pr_comments" => {
+            let number = &input["number"];
+            format!("get_pr_comments(#{number})")
+        }
+        "get_pr_files" => {
+            let number = &input["number"];
+            format!("get_pr_files(#{number})")
+        }
         "get_pr_diff" => {
             let number = &input["number"];
             format!("get_pr_diff(#{number})")
         }
+        "search_github" => {
+            let query = input["query"].as_str().unwrap_or("?");
+            format!("search_github({query})")
+        }
         _ => name.to_string(),
     }
 }
@@ -537,8 +545,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -569,8 +576,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: false,
             require_changelog: true,
@@ -609,8 +615,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -637,8 +642,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -664,8 +668,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -691,8 +694,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -717,8 +719,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: false,
@@ -759,8 +760,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -802,8 +802,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -845,8 +844,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -896,8 +894,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -943,8 +940,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -1022,8 +1018,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
@@ -1065,8 +1060,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: true,
             require_release_notes: true,
             require_changelog: true,
@@ -1117,8 +1111,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: true,
             require_release_notes: true,
             require_changelog: true,
@@ -1150,8 +1143,7 @@ mod tests {
             system: "",
             user_message: "",
             tool_defs: vec![],
-            repo_root: &tmp,
-            github: None,
+            tools: tools::ToolContext::new(&tmp, None),
             verify_links: false,
             require_release_notes: true,
             require_changelog: true,
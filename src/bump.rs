@@ -0,0 +1,538 @@
+//! `communique bump`: recommend the next version from the changes since the
+//! last release, for release scripts to read before tagging.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use log::info;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::forge::ForgeClient;
+use crate::git::Commit;
+use crate::github::PullRequest;
+use crate::llm::LlmClient;
+use crate::providers::Provider;
+use crate::tags::{Since, TagPattern, Version};
+use crate::{config, conventional, generate, git, offline, tags};
+
+const SYSTEM_PROMPT: &str = "You decide the next semantic version of a project from the changes since its last release. \
+Answer with exactly one of `major`, `minor` or `patch` on the first line, followed by one sentence explaining why. \
+major: users must change something to upgrade (removed or renamed APIs, flags, config, changed defaults). \
+minor: new functionality that is backwards compatible. \
+patch: bug fixes, performance, documentation and internal changes only.";
+
+pub struct BumpOptions {
+    pub tag: String,
+    pub prev_tag: Option<String>,
+    pub repo: Option<String>,
+    pub package: Option<String>,
+    pub no_ancestry_warning: bool,
+    pub llm: bool,
+    pub model: Option<String>,
+    pub provider: Option<Provider>,
+    pub base_url: Option<String>,
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Reason {
+    pub commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr: Option<u64>,
+    pub bump: Level,
+    /// What the level was read from: `conventional-commit`, `label`, `llm` or
+    /// `default`.
+    pub source: &'static str,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Recommendation {
+    pub previous_tag: Option<String>,
+    pub current_version: Option<String>,
+    pub bump: Level,
+    pub next_version: String,
+    /// `previous_tag` with its version replaced; unset when there is no
+    /// previous tag to model it on.
+    pub next_tag: Option<String>,
+    pub reasons: Vec<Reason>,
+}
+
+pub async fn run(opts: BumpOptions) -> miette::Result<()> {
+    let repo_root = git::repo_root()?;
+    let mut config = match &opts.config {
+        Some(path) => config::Config::load_from(path)?,
+        None => config::Config::load(&repo_root)?,
+    }
+    .unwrap_or_default();
+    let defaults = config.defaults.take().unwrap_or_default();
+
+    let package = opts
+        .package
+        .as_deref()
+        .map(|name| config.package(name))
+        .transpose()?;
+    let paths = package.map(|p| p.paths.clone()).unwrap_or_default();
+    let tag_pattern = package
+        .and_then(|p| p.tag_pattern.as_deref())
+        .or(config.tag_pattern.as_deref())
+        .map(TagPattern::new)
+        .transpose()?;
+
+    let prev_tag = match &opts.prev_tag {
+        Some(t) => t.clone(),
+        None => git::previous_tag(
+            &repo_root,
+            &opts.tag,
+            tag_pattern.as_ref(),
+            Since::Auto,
+            !opts.no_ancestry_warning,
+        )?,
+    };
+    info!("range: {prev_tag}..{}", opts.tag);
+
+    let forge = match generate::resolve_remote(&repo_root, opts.repo.as_deref(), &defaults) {
+        Ok(remote) => generate::forge_client(&remote, &defaults)?,
+        Err(e) => {
+            info!("not reading PR labels: {e}");
+            None
+        }
+    };
+    let client = if opts.llm {
+        Some(generate::build_llm_client(
+            opts.model,
+            None,
+            opts.provider,
+            opts.base_url,
+            &defaults,
+        )?)
+    } else {
+        None
+    };
+
+    let recommendation = recommend(
+        &repo_root,
+        &prev_tag,
+        &opts.tag,
+        &paths,
+        tag_pattern.as_ref(),
+        forge.as_ref(),
+        client.as_deref(),
+    )
+    .await?;
+    let json = serde_json::to_string_pretty(&recommendation)
+        .map_err(|e| Error::Parse(format!("failed to serialize recommendation: {e}")))?;
+    println!("{json}");
+    Ok(())
+}
+
+pub async fn recommend(
+    repo_root: &Path,
+    prev_tag: &str,
+    tag: &str,
+    paths: &[String],
+    pattern: Option<&TagPattern>,
+    forge: Option<&ForgeClient>,
+    client: Option<&dyn LlmClient>,
+) -> miette::Result<Recommendation> {
+    let commits = git::commits_between(repo_root, prev_tag, tag, paths)?;
+    let (commits, reverts) = git::pair_reverts(commits);
+    info!(
+        "found {} commits ({} reverted pairs dropped)",
+        commits.len(),
+        reverts.len()
+    );
+
+    let mut prs = HashMap::new();
+    if let Some(forge) = forge {
+        let mut numbers: Vec<u64> = commits
+            .iter()
+            .filter_map(offline::commit_pr_number)
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        let results =
+            futures_util::future::join_all(numbers.iter().map(|n| forge.get_pr(*n))).await;
+        for (number, result) in numbers.into_iter().zip(results) {
+            match result {
+                Ok(pr) => {
+                    prs.insert(number, pr);
+                }
+                Err(e) => info!("failed to fetch PR #{number}: {e}"),
+            }
+        }
+    }
+
+    // Commits that state their kind of change count as stated, even when it
+    // warrants no release (`chore:`). The rest fall back to the LLM or a
+    // patch, so one marked commit can't hide unmarked work beside it.
+    let mut reasons = Vec::new();
+    let mut unmarked = Vec::new();
+    for commit in &commits {
+        match classify(commit, &prs) {
+            Some(reason) if reason.bump == Level::None => {}
+            Some(reason) => reasons.push(reason),
+            None => unmarked.push(commit.clone()),
+        }
+    }
+    if !unmarked.is_empty() {
+        reasons.push(match client {
+            Some(client) => ask_llm(client, &unmarked, &prs).await?,
+            None => Reason {
+                commit: unmarked.last().map(|c| c.sha.clone()).unwrap_or_default(),
+                pr: None,
+                bump: Level::Patch,
+                source: "default",
+                reason: format!(
+                    "{} of {} commits don't mark their kind of change",
+                    unmarked.len(),
+                    commits.len()
+                ),
+            },
+        });
+    }
+    reasons.sort_by_key(|r| std::cmp::Reverse(r.bump));
+    let bump = reasons.first().map_or(Level::None, |r| r.bump);
+
+    let current = tags::tag_version(prev_tag, pattern);
+    let next = match &current {
+        None => Version::parse("0.1.0").unwrap(),
+        Some(v) => match bump {
+            Level::None => v.clone(),
+            Level::Patch => v.next_patch(),
+            Level::Minor => v.next_minor(),
+            // Before 1.0, breaking changes only bump the minor version.
+            Level::Major if v.major() == 0 => v.next_minor(),
+            Level::Major => v.next_major(),
+        },
+    };
+    let (previous_tag, next_tag) = match &current {
+        Some(_) => {
+            let version = match pattern {
+                Some(p) => p.version(prev_tag),
+                None => Some(tags::strip_package_prefix(prev_tag)),
+            }
+            .map(|s| s.trim_start_matches(['v', 'V']));
+            let next_tag = version
+                .and_then(|old| prev_tag.rfind(old).map(|pos| (pos, old)))
+                .map(|(pos, old)| {
+                    format!("{}{next}{}", &prev_tag[..pos], &prev_tag[pos + old.len()..])
+                });
+            (Some(prev_tag.to_string()), next_tag)
+        }
+        None if pattern.is_none() => (None, Some(format!("v{next}"))),
+        None => (None, None),
+    };
+
+    Ok(Recommendation {
+        previous_tag,
+        current_version: current.map(|v| v.to_string()),
+        bump,
+        next_version: next.to_string(),
+        next_tag,
+        reasons,
+    })
+}
+
+/// The level a commit states through its PR's labels or its Conventional
+/// Commit type, whichever is higher, or `None` when it says nothing.
+fn classify(commit: &Commit, prs: &HashMap<u64, PullRequest>) -> Option<Reason> {
+    let number = offline::commit_pr_number(commit);
+    let pr = number.and_then(|n| prs.get(&n));
+    let subject = pr.map_or(commit.subject.as_str(), |pr| pr.title.as_str());
+    let reason = |bump, source| Reason {
+        commit: commit.sha.clone(),
+        pr: number,
+        bump,
+        source,
+        reason: subject.to_string(),
+    };
+
+    let label = pr
+        .into_iter()
+        .flat_map(|pr| &pr.labels)
+        .filter_map(|l| label_level(&l.name))
+        .max()
+        .map(|level| reason(level, "label"));
+    let conventional = conventional::parse(subject, &commit.body).map(|cc| {
+        let level = if cc.breaking {
+            Level::Major
+        } else {
+            match cc.kind.as_str() {
+                "feat" => Level::Minor,
+                "fix" | "perf" | "security" | "revert" => Level::Patch,
+                _ => Level::None,
+            }
+        };
+        let mut reason = reason(level, "conventional-commit");
+        if let Some(note) = cc.breaking_note {
+            reason.reason = format!("{subject} — {note}");
+        }
+        reason
+    });
+    match (label, conventional) {
+        (Some(label), Some(cc)) if cc.bump > label.bump => Some(cc),
+        (Some(label), _) => Some(label),
+        (None, cc) => cc,
+    }
+}
+
+fn label_level(label: &str) -> Option<Level> {
+    let label = label.to_lowercase();
+    let label = label.rsplit([':', '/']).next().unwrap_or(&label).trim();
+    Some(match label {
+        "semver-major" | "major" | "breaking" | "breaking-change" => Level::Major,
+        "semver-minor" | "minor" | "feature" | "enhancement" | "feat" => Level::Minor,
+        "semver-patch" | "patch" | "bug" | "fix" | "bugfix" => Level::Patch,
+        _ => return None,
+    })
+}
+
+async fn ask_llm(
+    client: &dyn LlmClient,
+    commits: &[Commit],
+    prs: &HashMap<u64, PullRequest>,
+) -> miette::Result<Reason> {
+    let mut message = String::from("Changes since the last release:\n");
+    for commit in commits {
+        let pr = offline::commit_pr_number(commit).and_then(|n| prs.get(&n));
+        message.push_str(&format!("\n- {} {}", commit.sha, commit.subject));
+        if let Some(pr) = pr
+            && let Some(body) = pr.body.as_deref().filter(|b| !b.trim().is_empty())
+        {
+            let body: String = body.chars().take(500).collect();
+            message.push_str(&format!("\n  PR description: {}", body.replace('\n', " ")));
+        }
+    }
+
+    let mut conversation = client.new_conversation(&message);
+    let response = client
+        .send_turn(SYSTEM_PROMPT, &mut conversation, &[])
+        .await?;
+    let text = response.text.unwrap_or_default();
+    let text = text.trim();
+    let (first, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let bump = match first
+        .trim()
+        .trim_matches(|c: char| !c.is_ascii_alphabetic())
+        .to_lowercase()
+        .as_str()
+    {
+        "major" => Level::Major,
+        "minor" => Level::Minor,
+        "patch" => Level::Patch,
+        _ => {
+            return Err(Error::Llm(format!(
+                "expected major, minor or patch, got {first:?}"
+            )))?;
+        }
+    };
+    Ok(Reason {
+        commit: commits.last().map(|c| c.sha.clone()).unwrap_or_default(),
+        pr: None,
+        bump,
+        source: "llm",
+        reason: rest.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{StopReason, TurnResponse};
+    use crate::test_helpers::{MockLlmClient, TempRepo, fake_usage};
+
+    fn repo_with(subjects: &[&str]) -> TempRepo {
+        let repo = TempRepo::new();
+        repo.write_file("f.txt", "initial");
+        repo.commit("initial");
+        repo.tag("v1.2.3");
+        for (i, subject) in subjects.iter().enumerate() {
+            repo.write_file("f.txt", &i.to_string());
+            repo.commit(subject);
+        }
+        repo
+    }
+
+    async fn recommend_for(repo: &TempRepo, client: Option<&dyn LlmClient>) -> Recommendation {
+        recommend(repo.path(), "v1.2.3", "HEAD", &[], None, None, client)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_recommend_from_conventional_commits() {
+        let repo = repo_with(&["fix: crash", "feat: add --json", "chore: deps"]);
+        let rec = recommend_for(&repo, None).await;
+        assert_eq!(rec.bump, Level::Minor);
+        assert_eq!(rec.next_version, "1.3.0");
+        assert_eq!(rec.next_tag.as_deref(), Some("v1.3.0"));
+        let reasons: Vec<(&str, Level)> = rec
+            .reasons
+            .iter()
+            .map(|r| (r.reason.as_str(), r.bump))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                ("feat: add --json", Level::Minor),
+                ("fix: crash", Level::Patch)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recommend_breaking_change() {
+        let repo = repo_with(&["feat!: drop --legacy"]);
+        let rec = recommend_for(&repo, None).await;
+        assert_eq!(rec.bump, Level::Major);
+        assert_eq!(rec.next_version, "2.0.0");
+
+        let json = serde_json::to_value(&rec).unwrap();
+        assert_eq!(json["bump"], "major");
+        assert_eq!(json["previous_tag"], "v1.2.3");
+        assert_eq!(json["reasons"][0]["source"], "conventional-commit");
+    }
+
+    #[tokio::test]
+    async fn test_recommend_only_chores_is_none() {
+        let repo = repo_with(&["chore: deps", "ci: cache"]);
+        let rec = recommend_for(&repo, None).await;
+        assert_eq!(rec.bump, Level::None);
+        assert_eq!(rec.next_version, "1.2.3");
+        assert!(rec.reasons.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recommend_defaults_to_patch_without_markers() {
+        let repo = repo_with(&["Tidy up the parser"]);
+        let rec = recommend_for(&repo, None).await;
+        assert_eq!(rec.bump, Level::Patch);
+        assert_eq!(rec.reasons[0].source, "default");
+    }
+
+    #[tokio::test]
+    async fn test_recommend_asks_llm_without_markers() {
+        let repo = repo_with(&["Add a plugin system"]);
+        let client = MockLlmClient::new(vec![TurnResponse {
+            tool_calls: vec![],
+            text: Some("Minor\nAdds a plugin system without breaking anything.".into()),
+            stop_reason: StopReason::EndTurn,
+            usage: fake_usage(),
+        }]);
+        let rec = recommend_for(&repo, Some(&client)).await;
+        assert_eq!(rec.bump, Level::Minor);
+        assert_eq!(rec.reasons[0].source, "llm");
+        assert_eq!(
+            rec.reasons[0].reason,
+            "Adds a plugin system without breaking anything."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recommend_mixed_marked_and_unmarked_commits() {
+        let repo = repo_with(&["chore: deps", "Add plugin system"]);
+        let rec = recommend_for(&repo, None).await;
+        assert_eq!(rec.bump, Level::Patch);
+        assert_eq!(rec.reasons.len(), 1);
+        assert_eq!(rec.reasons[0].source, "default");
+        assert_eq!(
+            rec.reasons[0].reason,
+            "1 of 2 commits don't mark their kind of change"
+        );
+
+        let client = MockLlmClient::new(vec![TurnResponse {
+            tool_calls: vec![],
+            text: Some("Minor\nAdds a plugin system.".into()),
+            stop_reason: StopReason::EndTurn,
+            usage: fake_usage(),
+        }]);
+        let rec = recommend_for(&repo, Some(&client)).await;
+        assert_eq!(rec.bump, Level::Minor);
+        assert_eq!(rec.reasons[0].source, "llm");
+    }
+
+    #[tokio::test]
+    async fn test_recommend_keeps_package_prefix_and_pre_1_0_rule() {
+        let repo = TempRepo::new();
+        repo.write_file("f.txt", "0");
+        repo.commit("initial");
+        repo.tag("cli-v0.4.1");
+        repo.write_file("f.txt", "1");
+        repo.commit("feat!: new config format");
+
+        let pattern = TagPattern::new("cli-v*").unwrap();
+        let rec = recommend(
+            repo.path(),
+            "cli-v0.4.1",
+            "HEAD",
+            &[],
+            Some(&pattern),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rec.bump, Level::Major);
+        assert_eq!(rec.next_version, "0.5.0");
+        assert_eq!(rec.next_tag.as_deref(), Some("cli-v0.5.0"));
+    }
+
+    #[test]
+    fn test_classify_takes_higher_of_label_and_type() {
+        let commit = |subject: &str, body: &str| Commit {
+            sha: "abc1234".into(),
+            subject: subject.into(),
+            body: body.into(),
+        };
+        let pr = |title: &str, label: &str| -> PullRequest {
+            serde_json::from_value(serde_json::json!({
+                "number": 7,
+                "title": title,
+                "body": null,
+                "user": {"login": "dev"},
+                "labels": [{"name": label}],
+            }))
+            .unwrap()
+        };
+
+        let prs = HashMap::from([(7, pr("feat!: drop --legacy", "bug"))]);
+        let reason = classify(&commit("feat!: drop --legacy (#7)", ""), &prs).unwrap();
+        assert_eq!(reason.bump, Level::Major);
+        assert_eq!(reason.source, "conventional-commit");
+
+        let prs = HashMap::from([(7, pr("fix: parse flags", "bug"))]);
+        let reason = classify(
+            &commit(
+                "fix: parse flags (#7)",
+                "BREAKING CHANGE: flags are case-sensitive",
+            ),
+            &prs,
+        )
+        .unwrap();
+        assert_eq!(reason.bump, Level::Major);
+
+        let prs = HashMap::from([(7, pr("fix: parse flags", "semver-minor"))]);
+        let reason = classify(&commit("fix: parse flags (#7)", ""), &prs).unwrap();
+        assert_eq!(reason.bump, Level::Minor);
+        assert_eq!(reason.source, "label");
+    }
+
+    #[test]
+    fn test_label_level() {
+        assert_eq!(label_level("semver: major"), Some(Level::Major));
+        assert_eq!(label_level("type/enhancement"), Some(Level::Minor));
+        assert_eq!(label_level("bug"), Some(Level::Patch));
+        assert_eq!(label_level("documentation"), None);
+    }
+}
@@ -0,0 +1,813 @@
+//! Reading and updating CHANGELOG.md in the Keep a Changelog layout: an
+//! `## [Unreleased]` section followed by one `## [version]` section per
+//! release, newest first.
+
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy)]
+struct ChangelogSection {
+    body_start: usize,
+    body_end: usize,
+}
+
+fn is_unreleased_header(line: &str) -> bool {
+    let line = line.trim();
+    line == "## Unreleased" || line == "## [Unreleased]" || line.starts_with("## [Unreleased](")
+}
+
+fn is_forbidden_unreleased_target_header(line: &str) -> bool {
+    let line = line.trim();
+    line == "## Unreleased"
+        || line == "## [Unreleased]"
+        || line.starts_with("## [Unreleased](")
+        || line == "## HEAD"
+        || line == "## [HEAD]"
+        || line.starts_with("## [HEAD](")
+}
+
+fn is_version_header(line: &str) -> bool {
+    header_version(line).is_some()
+}
+
+fn header_version(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix("## ")?;
+    let rest = rest.trim_start();
+
+    if let Some(bracketed) = rest.strip_prefix('[') {
+        let (version, _) = bracketed.split_once(']')?;
+        return is_version_like(version).then_some(version);
+    }
+
+    let version = rest
+        .split_once(char::is_whitespace)
+        .map_or(rest, |(version, _)| version);
+    is_version_like(version).then_some(version)
+}
+
+fn is_version_like(value: &str) -> bool {
+    let value = crate::tags::strip_package_prefix(value);
+    let value = value.strip_prefix('v').unwrap_or(value);
+    value.chars().next().is_some_and(|c| c.is_ascii_digit()) && value.contains('.')
+}
+
+trait LinesWithOffset {
+    fn lines_with_offset(&self) -> LinesWithOffsetIter<'_>;
+}
+
+impl LinesWithOffset for str {
+    fn lines_with_offset(&self) -> LinesWithOffsetIter<'_> {
+        LinesWithOffsetIter {
+            content: self,
+            offset: 0,
+        }
+    }
+}
+
+struct LinesWithOffsetIter<'a> {
+    content: &'a str,
+    offset: usize,
+}
+
+impl<'a> Iterator for LinesWithOffsetIter<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.content.len() {
+            return None;
+        }
+
+        let start = self.offset;
+        let rest = &self.content[start..];
+        let line_len = rest.find('\n').map_or(rest.len(), |idx| idx + 1);
+        self.offset += line_len;
+        Some((start, &self.content[start..start + line_len]))
+    }
+}
+
+fn find_version_section_start(contents: &str, version: &str) -> Option<usize> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+
+    contents
+        .lines_with_offset()
+        .find(|(_, line)| {
+            header_version(line)
+                .map(|header| header.strip_prefix('v').unwrap_or(header) == version)
+                .unwrap_or(false)
+        })
+        .map(|(start, _)| start)
+}
+
+fn find_next_version_section_start(contents: &str, after: usize) -> Option<usize> {
+    contents
+        .lines_with_offset()
+        .find(|(start, line)| *start > after && is_version_header(line))
+        .map(|(start, _)| start)
+}
+
+fn join_changelog_head_tail(head: &str, tail: &str) -> String {
+    let head = head.trim_end();
+    let tail = tail.trim_start_matches(['\r', '\n']);
+
+    if tail.is_empty() {
+        head.to_string()
+    } else if head.is_empty() {
+        tail.to_string()
+    } else {
+        format!("{head}\n\n{tail}")
+    }
+}
+
+fn format_version_header(
+    existing: &str,
+    tag: &str,
+    version: &str,
+    date: &str,
+    release_url: &str,
+) -> String {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let template = existing
+        .lines_with_offset()
+        .find(|(_, line)| is_version_header(line))
+        .map(|(_, line)| line.trim());
+
+    let Some(template) = template else {
+        return format!("## [{version}]({release_url}) - {date}");
+    };
+
+    // A changelog headed by full tags (`## cli-v1.3.0`) keeps getting them.
+    let version = match header_version(template) {
+        Some(header) if crate::tags::strip_package_prefix(header) != header => tag,
+        _ => version,
+    };
+
+    if template.starts_with("## [") {
+        if template.contains("](") {
+            if template.contains(" - ") {
+                format!("## [{version}]({release_url}) - {date}")
+            } else {
+                format!("## [{version}]({release_url})")
+            }
+        } else if template.contains(" - ") {
+            format!("## [{version}] - {date}")
+        } else {
+            format!("## [{version}]")
+        }
+    } else {
+        let prefix = if template.trim_start_matches("## ").starts_with('v')
+            && version.starts_with(|c: char| c.is_ascii_digit())
+        {
+            "v"
+        } else {
+            ""
+        };
+        if template.contains(" - ") {
+            format!("## {prefix}{version} - {date}")
+        } else {
+            format!("## {prefix}{version}")
+        }
+    }
+}
+
+fn remove_version_section(contents: &str, version: &str) -> String {
+    let Some(start) = find_version_section_start(contents, version) else {
+        return contents.to_string();
+    };
+    let end = find_next_version_section_start(contents, start).unwrap_or(contents.len());
+    join_changelog_head_tail(&contents[..start], &contents[end..])
+}
+
+pub fn upsert_version(
+    existing: &str,
+    tag: &str,
+    version: &str,
+    date: &str,
+    release_url: &str,
+    changelog: &str,
+) -> miette::Result<String> {
+    let existing = repair_changelog_section_boundaries(existing);
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let header = format_version_header(&existing, tag, version, date, release_url);
+    let body = changelog.trim();
+    let section = if body.is_empty() {
+        header
+    } else {
+        format!("{header}\n\n{body}")
+    };
+
+    // The entry may be headed by the full tag or just its version.
+    let without_existing = remove_version_section(&remove_version_section(&existing, tag), version);
+    let Some(unreleased) = find_unreleased_section(&without_existing)? else {
+        return Err(miette::miette!(
+            "CHANGELOG.md exists but does not contain ## [Unreleased] or ## Unreleased; add an Unreleased section before running `communique generate {} --changelog`.",
+            tag
+        ));
+    };
+
+    let before = without_existing[..unreleased.body_end].trim_end();
+    let after = without_existing[unreleased.body_end..].trim_start_matches(['\r', '\n']);
+    let updated = if after.is_empty() {
+        format!("{before}\n\n{section}")
+    } else {
+        format!("{before}\n\n{section}\n\n{after}")
+    };
+
+    Ok(format!("{}\n", updated.trim_end()))
+}
+
+fn repair_changelog_section_boundaries(content: &str) -> String {
+    let mut repaired = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(pos) = rest.find(")## ") {
+        let (before, after_marker) = rest.split_at(pos + 1);
+        repaired.push_str(before);
+        if is_version_header(after_marker.lines().next().unwrap_or("")) {
+            repaired.push_str("\n\n");
+        }
+        rest = after_marker;
+    }
+
+    repaired.push_str(rest);
+    repaired
+}
+
+pub fn read_entry(path: &Path, tag: &str) -> Option<String> {
+    let contents = xx::file::read_to_string(path).ok()?;
+
+    let start = find_version_section_start(&contents, tag)?;
+    let end = find_next_version_section_start(&contents, start).unwrap_or(contents.len());
+
+    Some(contents[start..end].trim().to_string())
+}
+
+fn find_unreleased_section(contents: &str) -> miette::Result<Option<ChangelogSection>> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in contents.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        lines.push((start, offset, line));
+    }
+
+    let mut unreleased_index = None;
+    for (index, (_, _, line)) in lines.iter().enumerate() {
+        if is_unreleased_header(line) && unreleased_index.replace(index).is_some() {
+            return Err(miette::miette!(
+                "CHANGELOG.md contains multiple Unreleased sections; cannot update safely."
+            ));
+        }
+    }
+
+    let Some(index) = unreleased_index else {
+        return Ok(None);
+    };
+
+    let body_start = lines[index].1;
+    let mut body_end = contents.len();
+    for (start, _, line) in lines.iter().skip(index + 1) {
+        if is_version_header(line) {
+            body_end = *start;
+            break;
+        }
+    }
+
+    Ok(Some(ChangelogSection {
+        body_start,
+        body_end,
+    }))
+}
+
+fn normalize_generated_changelog_body(generated: &str) -> String {
+    generated
+        .lines()
+        .filter(|line| !is_forbidden_unreleased_target_header(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+pub fn new_unreleased(generated: &str) -> String {
+    let body = normalize_generated_changelog_body(generated);
+    if body.is_empty() {
+        "# Changelog\n\n## [Unreleased]\n".to_string()
+    } else {
+        format!("# Changelog\n\n## [Unreleased]\n\n{body}\n")
+    }
+}
+
+pub fn replace_unreleased_section(existing: &str, generated: &str) -> miette::Result<String> {
+    let Some(section) = find_unreleased_section(existing)? else {
+        return Err(miette::miette!(
+            "CHANGELOG.md exists but does not contain ## [Unreleased] or ## Unreleased; add an Unreleased section before running `communique generate HEAD --changelog`."
+        ));
+    };
+
+    let body = normalize_generated_changelog_body(generated);
+    let mut updated = existing[..section.body_start].trim_end().to_string();
+
+    if !body.is_empty() {
+        updated.push_str("\n\n");
+        updated.push_str(&body);
+    }
+
+    let tail = existing[section.body_end..].trim_start_matches(['\r', '\n']);
+    if !tail.is_empty() {
+        updated.push_str("\n\n");
+        updated.push_str(tail);
+    }
+
+    Ok(format!("{}\n", updated.trim_end()))
+}
+
+pub fn read_unreleased_section(path: &Path) -> miette::Result<Option<String>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(crate::error::Error::Io(err).into()),
+    };
+
+    let Some(section) = find_unreleased_section(&contents)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        contents[section.body_start..section.body_end]
+            .trim()
+            .to_string(),
+    ))
+}
+
+/// Split changelog content into (head, tail) keeping at most `max_versions` versioned sections
+/// in the head. This limits tokens sent to the LLM for large changelogs.
+#[cfg(test)]
+fn split_changelog(content: &str, max_versions: usize) -> (&str, &str) {
+    let mut version_count = 0;
+
+    for (start, line) in content.lines_with_offset() {
+        if !is_version_header(line) {
+            continue;
+        }
+
+        version_count += 1;
+        if version_count > max_versions {
+            return (&content[..start], &content[start..]);
+        }
+    }
+
+    (content, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_changelog_entry_found() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "## [1.0.0]\n### Added\n- Feature\n\n## [0.9.0]\n### Fixed\n- Bug\n",
+        )
+        .unwrap();
+        let entry = read_entry(&dir.path().join("CHANGELOG.md"), "v1.0.0").unwrap();
+        assert!(entry.contains("### Added"));
+        assert!(entry.contains("Feature"));
+        assert!(!entry.contains("0.9.0"));
+    }
+
+    #[test]
+    fn test_read_changelog_entry_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("CHANGELOG.md"), "## [0.9.0]\n- old\n").unwrap();
+        let entry = read_entry(&dir.path().join("CHANGELOG.md"), "v2.0.0");
+        assert!(entry.is_none());
+    }
+
+    #[test]
+    fn test_read_changelog_entry_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = read_entry(&dir.path().join("CHANGELOG.md"), "v1.0.0");
+        assert!(entry.is_none());
+    }
+
+    #[test]
+    fn test_read_changelog_entry_alt_format() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "## 1.0.0\n### Changed\n- Something\n",
+        )
+        .unwrap();
+        let entry = read_entry(&dir.path().join("CHANGELOG.md"), "v1.0.0").unwrap();
+        assert!(entry.contains("### Changed"));
+    }
+
+    #[test]
+    fn test_read_changelog_entry_package_prefixed_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "# Changelog\n\n## cli-v1.4.0\n- CLI change\n\n## sdk-v1.4.0\n- SDK change\n\n## cli-v1.3.0\n- Older\n",
+        )
+        .unwrap();
+        let entry = read_entry(&dir.path().join("CHANGELOG.md"), "cli-v1.4.0").unwrap();
+        assert_eq!(entry, "## cli-v1.4.0\n- CLI change");
+        assert!(read_entry(&dir.path().join("CHANGELOG.md"), "1.4.0").is_none());
+    }
+
+    #[test]
+    fn test_upsert_version_changelog_uses_package_version() {
+        let existing = "# Changelog\n\n## [Unreleased]\n\n## [1.4.0] - 2024-01-01\n\n- Stale\n\n## [1.3.0] - 2023-12-01\n\n- Older\n";
+        let updated = upsert_version(
+            existing,
+            "cli-v1.4.0",
+            "1.4.0",
+            "2024-02-01",
+            "https://github.com/o/r/releases/tag/cli-v1.4.0",
+            "- Fresh",
+        )
+        .unwrap();
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## [Unreleased]\n\n## [1.4.0] - 2024-02-01\n\n- Fresh\n\n## [1.3.0] - 2023-12-01\n\n- Older\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_version_changelog_shared_by_packages_at_same_version() {
+        let existing = "# Changelog\n\n## [Unreleased]\n\n## [1.3.0] - 2023-12-01\n\n- Older\n";
+        let cli = upsert_version(
+            existing,
+            "cli-v1.4.0",
+            "cli-v1.4.0",
+            "2024-02-01",
+            "https://github.com/o/r/releases/tag/cli-v1.4.0",
+            "- CLI change",
+        )
+        .unwrap();
+        let both = upsert_version(
+            &cli,
+            "sdk-v1.4.0",
+            "sdk-v1.4.0",
+            "2024-02-02",
+            "https://github.com/o/r/releases/tag/sdk-v1.4.0",
+            "- SDK change",
+        )
+        .unwrap();
+        assert_eq!(
+            both,
+            "# Changelog\n\n## [Unreleased]\n\n## [sdk-v1.4.0] - 2024-02-02\n\n- SDK change\n\n## [cli-v1.4.0] - 2024-02-01\n\n- CLI change\n\n## [1.3.0] - 2023-12-01\n\n- Older\n"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CHANGELOG.md");
+        std::fs::write(&path, &both).unwrap();
+        assert_eq!(
+            read_entry(&path, "cli-v1.4.0").unwrap(),
+            "## [cli-v1.4.0] - 2024-02-01\n\n- CLI change"
+        );
+        assert!(read_entry(&path, "1.4.0").is_none());
+    }
+
+    #[test]
+    fn test_format_version_header_keeps_package_tag_template() {
+        assert_eq!(
+            format_version_header(
+                "# Changelog\n\n## cli-v1.3.0\n",
+                "cli-v1.4.0",
+                "1.4.0",
+                "2025-02-01",
+                "https://github.com/test/repo/releases/tag/cli-v1.4.0",
+            ),
+            "## cli-v1.4.0"
+        );
+    }
+
+    #[test]
+    fn test_read_changelog_entry_allows_level_two_categories() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "## [1.0.0]\n## Added\n- Feature\n\n## Fixed\n- Bug\n\n## [0.9.0]\n## Fixed\n- Old\n",
+        )
+        .unwrap();
+
+        let entry = read_entry(&dir.path().join("CHANGELOG.md"), "v1.0.0").unwrap();
+        assert!(entry.contains("## Added"));
+        assert!(entry.contains("## Fixed\n- Bug"));
+        assert!(!entry.contains("0.9.0"));
+    }
+
+    #[test]
+    fn test_read_unreleased_section_bracketed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "# Changelog\n\n## [Unreleased]\n\n### Changed\n- Draft\n\n## [1.0.0]\n- Old\n",
+        )
+        .unwrap();
+
+        let entry = read_unreleased_section(&dir.path().join("CHANGELOG.md"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry, "### Changed\n- Draft");
+        assert!(!entry.contains("1.0.0"));
+    }
+
+    #[test]
+    fn test_read_unreleased_section_plain() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "# Changelog\n\n## Unreleased\n\n### Fixed\n- Draft bug\n\n## 1.0.0\n- Old\n",
+        )
+        .unwrap();
+
+        let entry = read_unreleased_section(&dir.path().join("CHANGELOG.md"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry, "### Fixed\n- Draft bug");
+    }
+
+    #[test]
+    fn test_read_unreleased_section_missing_file_or_section() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            read_unreleased_section(&dir.path().join("CHANGELOG.md"))
+                .unwrap()
+                .is_none()
+        );
+
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "# Changelog\n\n## [1.0.0]\n- Old\n",
+        )
+        .unwrap();
+        assert!(
+            read_unreleased_section(&dir.path().join("CHANGELOG.md"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_split_changelog_small() {
+        let content =
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2025-01-01\n### Added\n- Feature\n";
+        let (head, tail) = split_changelog(content, 3);
+        assert_eq!(head, content);
+        assert_eq!(tail, "");
+    }
+
+    #[test]
+    fn test_split_changelog_large() {
+        let content = "\
+# Changelog
+
+## [Unreleased]
+
+## [3.0.0] - 2025-03-01
+### Added
+- Three
+
+## [2.0.0] - 2025-02-01
+### Added
+- Two
+
+## [1.0.0] - 2025-01-01
+### Added
+- One
+
+## [0.9.0] - 2024-12-01
+### Fixed
+- Zero nine
+";
+        let (head, tail) = split_changelog(content, 3);
+        assert!(head.contains("[3.0.0]"));
+        assert!(head.contains("[2.0.0]"));
+        assert!(head.contains("[1.0.0]"));
+        assert!(!head.contains("[0.9.0]"));
+        assert!(tail.contains("[0.9.0]"));
+    }
+
+    #[test]
+    fn test_split_changelog_ignores_level_two_categories() {
+        let content = "\
+# Changelog
+
+## [Unreleased]
+
+## [3.0.0] - 2025-03-01
+## Added
+- Three
+
+## Fixed
+- Three fix
+
+## [2.0.0] - 2025-02-01
+## Added
+- Two
+
+## [1.0.0] - 2025-01-01
+## Added
+- One
+
+## [0.9.0] - 2024-12-01
+## Fixed
+- Zero nine
+";
+
+        let (head, tail) = split_changelog(content, 3);
+        assert!(head.contains("## Added\n- Three"));
+        assert!(head.contains("## Fixed\n- Three fix"));
+        assert!(head.contains("[1.0.0]"));
+        assert!(!head.contains("[0.9.0]"));
+        assert!(tail.starts_with("## [0.9.0]"));
+    }
+
+    #[test]
+    fn test_split_changelog_ignores_digit_leading_non_versions() {
+        let content = "\
+# Changelog
+
+## [Unreleased]
+
+## [3.0.0]
+- Three
+
+## 2024 Retrospective
+- Not a version
+
+## 1st Quarter Updates
+- Not a version either
+
+## [2.0.0]
+- Two
+
+## [1.0.0]
+- One
+
+## [0.9.0]
+- Zero nine
+";
+
+        let (head, tail) = split_changelog(content, 3);
+        assert!(head.contains("2024 Retrospective"));
+        assert!(head.contains("1st Quarter Updates"));
+        assert!(!head.contains("[0.9.0]"));
+        assert!(tail.starts_with("## [0.9.0]"));
+    }
+
+    #[test]
+    fn test_repair_changelog_section_boundaries() {
+        let content = "- Fix one ([#1](https://github.com/jdx/communique/pull/1))## [1.0.0]\n## Fixed\n- Old ([#0](https://github.com/jdx/communique/pull/0))## [0.9.0]\n";
+
+        let repaired = repair_changelog_section_boundaries(content);
+
+        assert!(repaired.contains("pull/1))\n\n## [1.0.0]"));
+        assert!(repaired.contains("pull/0))\n\n## [0.9.0]"));
+    }
+
+    #[test]
+    fn test_repair_changelog_section_boundaries_ignores_non_versions() {
+        let content = "- Fixed `pattern()## anchor` without changing inline code\n- Also fixed link text)## not a version\n";
+
+        let repaired = repair_changelog_section_boundaries(content);
+
+        assert_eq!(repaired, content);
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_bracketed_preserves_tail() {
+        let existing = "# Changelog\n\n## [Unreleased]\n\n### Changed\n- Old draft\n\n## [1.0.0] - 2025-01-01\n### Added\n- Old release\n";
+        let updated = replace_unreleased_section(existing, "### Added\n- New draft").unwrap();
+
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n- New draft\n\n## [1.0.0] - 2025-01-01\n### Added\n- Old release\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_plain_preserves_header() {
+        let existing = "# Changelog\n\n## Unreleased\n\n- Old draft\n";
+        let updated = replace_unreleased_section(existing, "### Fixed\n- New fix").unwrap();
+
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## Unreleased\n\n### Fixed\n- New fix\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_allows_level_two_categories() {
+        let existing = "# Changelog\n\n## [Unreleased]\n\n## Added\n- thing 1\n\n## Fixed\n- thing 2\n\n## [1.0.0] - 2026-01-01\n- old release\n";
+        let updated = replace_unreleased_section(existing, "## Added\n- replacement").unwrap();
+
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## [Unreleased]\n\n## Added\n- replacement\n\n## [1.0.0] - 2026-01-01\n- old release\n"
+        );
+        assert!(!updated.contains("thing 1"));
+        assert!(!updated.contains("thing 2"));
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_stops_at_unbracketed_v_version() {
+        let existing =
+            "# Changelog\n\n## [Unreleased]\n\n## Added\n- old draft\n\n## v1.0.0\n- old release\n";
+        let updated = replace_unreleased_section(existing, "## Fixed\n- replacement").unwrap();
+
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## [Unreleased]\n\n## Fixed\n- replacement\n\n## v1.0.0\n- old release\n"
+        );
+        assert!(!updated.contains("old draft"));
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_preserves_linked_header() {
+        let existing = "# Changelog\n\n## [Unreleased](https://example.com/compare/v1.0.0...HEAD)\n\n- Old draft\n";
+        let updated = replace_unreleased_section(existing, "- New draft").unwrap();
+
+        assert_eq!(
+            updated,
+            "# Changelog\n\n## [Unreleased](https://example.com/compare/v1.0.0...HEAD)\n\n- New draft\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_removes_forbidden_target_headers() {
+        let existing = "# Changelog\n\n## [Unreleased]\n\n- Old\n";
+        let updated = replace_unreleased_section(existing, "## [HEAD]\n### Added\n- New").unwrap();
+
+        assert!(!updated.contains("## [HEAD]"));
+        assert!(!updated.contains("## HEAD"));
+        assert!(updated.contains("### Added\n- New"));
+    }
+
+    #[test]
+    fn test_format_version_header_matches_existing_styles() {
+        assert_eq!(
+            format_version_header(
+                "# Changelog\n\n## [1.0.0](https://example.com) - 2025-01-01\n",
+                "v1.1.0",
+                "v1.1.0",
+                "2025-02-01",
+                "https://github.com/test/repo/releases/tag/v1.1.0",
+            ),
+            "## [1.1.0](https://github.com/test/repo/releases/tag/v1.1.0) - 2025-02-01"
+        );
+        assert_eq!(
+            format_version_header(
+                "# Changelog\n\n## v1.0.0 - 2025-01-01\n",
+                "v1.1.0",
+                "1.1.0",
+                "2025-02-01",
+                "https://github.com/test/repo/releases/tag/v1.1.0",
+            ),
+            "## v1.1.0 - 2025-02-01"
+        );
+        assert_eq!(
+            format_version_header(
+                "# Changelog\n\n## [1.0.0]\n",
+                "v1.1.0",
+                "1.1.0",
+                "2025-02-01",
+                "https://github.com/test/repo/releases/tag/v1.1.0",
+            ),
+            "## [1.1.0]"
+        );
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_errors_without_unreleased_header() {
+        let err = replace_unreleased_section("# Changelog\n\n## [1.0.0]\n- Old\n", "- New")
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("does not contain ## [Unreleased] or ## Unreleased"));
+    }
+
+    #[test]
+    fn test_replace_unreleased_section_errors_on_duplicate_unreleased_headers() {
+        let err = replace_unreleased_section(
+            "# Changelog\n\n## [Unreleased]\n\n## Unreleased\n",
+            "- New",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("multiple Unreleased sections"));
+    }
+
+    #[test]
+    fn test_normalize_generated_changelog_body_removes_linked_head_header() {
+        let body = normalize_generated_changelog_body(
+            "## [HEAD](https://example.com/compare/v1.0.0...HEAD)\n## Added\n- New",
+        );
+
+        assert_eq!(body, "## Added\n- New");
+    }
+}
@@ -38,9 +38,25 @@ pub enum Command {
         /// Previous tag (auto-detected if omitted)
         prev_tag: Option<String>,
 
-        /// Push editorialized notes to the GitHub release
+        /// Push editorialized notes to the release on GitHub, GitLab, Gitea or Forgejo
+        #[arg(long, alias = "github-release")]
+        publish_release: bool,
+
+        /// Create the release when the tag has none (implies --publish-release)
+        #[arg(long)]
+        create_release: bool,
+
+        /// Create the release as a draft
+        #[arg(long)]
+        draft: bool,
+
+        /// Create the release as a prerelease (inferred from a semver suffix like -rc.1)
         #[arg(long)]
-        github_release: bool,
+        prerelease: bool,
+
+        /// Mark the created release as the latest, even on an older version line
+        #[arg(long)]
+        make_latest: bool,
 
         /// Update CHANGELOG.md with the generated changelog entry
         #[arg(long)]
@@ -77,6 +93,64 @@ pub enum Command {
         /// Write output to a file instead of stdout
         #[arg(long, short)]
         output: Option<PathBuf>,
+
+        /// Build notes from commit subjects and PR titles without calling an LLM
+        #[arg(long, conflicts_with_all = ["model", "max_tokens", "provider", "base_url"])]
+        no_llm: bool,
+
+        /// Release a package defined under [packages.<name>] in communique.toml
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Compare against the previous stable tag, even for a prerelease
+        #[arg(long, conflicts_with_all = ["prev_tag", "since_previous"])]
+        since_stable: bool,
+
+        /// Compare against the previous tag, even a prerelease when releasing a stable version
+        #[arg(long, conflicts_with = "prev_tag")]
+        since_previous: bool,
+
+        /// Don't warn about newer tags that aren't ancestors of the release, like patches on a maintenance branch
+        #[arg(long, conflicts_with = "prev_tag")]
+        no_ancestry_warning: bool,
+    },
+
+    /// Recommend the next semver version from the changes since the last tag, as JSON
+    Bump {
+        /// Ref to be released
+        #[arg(default_value = "HEAD")]
+        tag: String,
+
+        /// Previous tag (auto-detected if omitted)
+        prev_tag: Option<String>,
+
+        /// GitHub repo (owner/repo), used to read PR labels when GITHUB_TOKEN is set
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// Only consider a package defined under [packages.<name>] in communique.toml
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Don't warn about newer tags that aren't ancestors of the release, like patches on a maintenance branch
+        #[arg(long, conflicts_with = "prev_tag")]
+        no_ancestry_warning: bool,
+
+        /// Ask the LLM about commits whose message or PR label doesn't state their kind of change
+        #[arg(long)]
+        llm: bool,
+
+        /// LLM model to use with --llm
+        #[arg(long, requires = "llm")]
+        model: Option<String>,
+
+        /// LLM provider (anthropic or openai, auto-detected from model if omitted)
+        #[arg(long, requires = "llm")]
+        provider: Option<Provider>,
+
+        /// Base URL for the LLM API
+        #[arg(long, requires = "llm")]
+        base_url: Option<String>,
     },
 
     /// Generate a communique.toml config file in the repo root
@@ -26,6 +26,7 @@ use clap_usage::usage::SpecCommandEffect::{self, Destructive, Read, Write};
 /// Commands whose effect is fixed, keyed by their full path under `communique`.
 pub const EFFECTS: &[(&str, SpecCommandEffect)] = &[
     // Bare `generate` only prints; the danger is in its flags, below.
+    ("bump", Read),
     ("generate", Read),
     ("init", Write),
     ("sponsors", Read),
@@ -42,7 +43,9 @@ pub const FLAG_EFFECTS: &[(&str, &str, SpecCommandEffect)] = &[
     // Rewrites CHANGELOG.md in place.
     ("generate", "changelog", Write),
     // Replaces the body of an already-published GitHub release.
-    ("generate", "github-release", Write),
+    ("generate", "publish-release", Write),
+    // Creates a release on the forge when the tag has none.
+    ("generate", "create-release", Write),
     ("generate", "output", Write),
     // Overwrites an existing communique.toml.
     ("init", "force", Destructive),
@@ -156,7 +159,7 @@ mod tests {
                 .unwrap_or_else(|| panic!("no --{name}"))
         };
         assert_eq!(flag("changelog").effect, Some(Write));
-        assert_eq!(flag("github-release").effect, Some(Write));
+        assert_eq!(flag("publish-release").effect, Some(Write));
         // A flag with no entry must be left alone rather than inheriting one.
         assert_eq!(flag("concise").effect, None);
 
@@ -1,17 +1,41 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use serde::Deserialize;
 
 use crate::error::Result;
+use crate::forge::ForgeKind;
 
 pub const DEFAULT_MAX_TOKENS: u32 = 16_384;
+pub const DEFAULT_TOOL_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_TOOL_MAX_OUTPUT: usize = 50_000;
+pub const DEFAULT_GREP_MAX_MATCHES: usize = 50;
 use crate::providers::Provider;
 
 #[derive(Debug, Deserialize, Default)]
 pub struct Config {
     pub system_extra: Option<String>,
     pub context: Option<String>,
+    /// Glob or regex selecting this package's tags, for repos that tag
+    /// several packages. See [`crate::tags::TagPattern`].
+    pub tag_pattern: Option<String>,
     pub defaults: Option<Defaults>,
+    #[serde(default)]
+    pub tools: ToolsConfig,
+    /// Packages in a monorepo, released one at a time with `--package`.
+    #[serde(default)]
+    pub packages: BTreeMap<String, PackageConfig>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct PackageConfig {
+    /// Pathspecs, relative to the repo root, whose commits belong to the package.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Changelog to read and update instead of `CHANGELOG.md`.
+    pub changelog: Option<String>,
+    /// Overrides the top-level `tag_pattern` for this package.
+    pub tag_pattern: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -24,6 +48,167 @@ pub struct Defaults {
     pub emoji: Option<bool>,
     pub verify_links: Option<bool>,
     pub match_style: Option<bool>,
+    /// Group the git log by Conventional Commit type in the prompt. Unset
+    /// means detect it from the commits in the range.
+    pub conventional_commits: Option<bool>,
+    /// Let `--publish-release` create the release when the tag has none, as
+    /// `--create-release` does.
+    pub create_release: Option<bool>,
+    /// Which forge hosts the repo, for hosts whose name doesn't say.
+    pub forge: Option<ForgeKind>,
+    /// GitHub Enterprise Server host. Remotes on it are taken to be GitHub,
+    /// and it is used when the remote doesn't name a host (e.g. with
+    /// `repo = "owner/repo"` and no origin).
+    pub github_host: Option<String>,
+    /// GitHub API base URL. Defaults to `https://<github_host>/api/v3` for
+    /// Enterprise Server and `https://api.github.com` otherwise.
+    pub github_api_url: Option<String>,
+}
+
+/// Which tools the agent may use, plus any project-specific ones.
+///
+/// Accepts either a bare `[[tools]]` list of custom tools or a `[tools]` table
+/// with access controls, in which case custom tools move to `[[tools.custom]]`
+/// (TOML cannot mix the two forms under one key).
+#[derive(Debug, Deserialize, Default)]
+#[serde(from = "ToolsSection")]
+pub struct ToolsConfig {
+    /// When set, only these tools are offered (`submit_release_notes` always is).
+    pub enabled: Option<Vec<String>>,
+    pub disabled: Vec<String>,
+    /// Path globs, relative to the repo root, that the tools never read:
+    /// `read_file` refuses them and grep, diffs and custom tool output skip them.
+    pub read_deny: Vec<String>,
+    /// Per-file match cap for `grep`.
+    pub grep_max_matches: Option<usize>,
+    /// Use ripgrep instead of git grep when searching the working tree.
+    pub use_ripgrep: bool,
+    pub custom: Vec<CustomTool>,
+}
+
+pub static DEFAULT_TOOLS_CONFIG: ToolsConfig = ToolsConfig {
+    enabled: None,
+    disabled: Vec::new(),
+    read_deny: Vec::new(),
+    grep_max_matches: None,
+    use_ripgrep: false,
+    custom: Vec::new(),
+};
+
+impl ToolsConfig {
+    pub fn grep_max_matches(&self) -> usize {
+        self.grep_max_matches.unwrap_or(DEFAULT_GREP_MAX_MATCHES)
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        if name == "submit_release_notes" {
+            return true;
+        }
+        if self.disabled.iter().any(|d| d == name) {
+            return false;
+        }
+        match &self.enabled {
+            Some(enabled) => enabled.iter().any(|e| e == name),
+            None => true,
+        }
+    }
+}
+
+enum ToolsSection {
+    List(Vec<CustomTool>),
+    Table(ToolsTable),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolsTable {
+    enabled: Option<Vec<String>>,
+    #[serde(default)]
+    disabled: Vec<String>,
+    #[serde(default)]
+    read_deny: Vec<String>,
+    grep_max_matches: Option<usize>,
+    #[serde(default)]
+    use_ripgrep: bool,
+    #[serde(default)]
+    custom: Vec<CustomTool>,
+}
+
+// Picks the form from the TOML shape rather than `#[serde(untagged)]`, so a
+// bad `[tools]` table reports the offending key.
+impl<'de> Deserialize<'de> for ToolsSection {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = ToolsSection;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a [[tools]] list or a [tools] table")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                seq: A,
+            ) -> std::result::Result<ToolsSection, A::Error> {
+                Deserialize::deserialize(serde::de::value::SeqAccessDeserializer::new(seq))
+                    .map(ToolsSection::List)
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> std::result::Result<ToolsSection, A::Error> {
+                Deserialize::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(ToolsSection::Table)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl From<ToolsSection> for ToolsConfig {
+    fn from(section: ToolsSection) -> Self {
+        match section {
+            ToolsSection::List(custom) => ToolsConfig {
+                custom,
+                ..ToolsConfig::default()
+            },
+            ToolsSection::Table(table) => ToolsConfig {
+                enabled: table.enabled,
+                disabled: table.disabled,
+                read_deny: table.read_deny,
+                grep_max_matches: table.grep_max_matches,
+                use_ripgrep: table.use_ripgrep,
+                custom: table.custom,
+            },
+        }
+    }
+}
+
+/// A project-specific tool backed by a command, declared as `[[tools]]`.
+///
+/// `command` is an argv template: `{field}` placeholders are replaced with
+/// values from the tool input, and the result is executed directly — never
+/// through a shell — from the repo root.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CustomTool {
+    pub name: String,
+    pub description: String,
+    #[serde(default = "empty_input_schema")]
+    pub input_schema: serde_json::Value,
+    pub command: Vec<String>,
+    /// Seconds before the command is killed.
+    pub timeout: Option<u64>,
+    /// Bytes of output returned to the model before truncating.
+    pub max_output: Option<usize>,
+}
+
+fn empty_input_schema() -> serde_json::Value {
+    serde_json::json!({"type": "object", "properties": {}})
 }
 
 const TEMPLATE: &str = r#"# Extra instructions appended to the system prompt.
@@ -34,6 +219,10 @@ const TEMPLATE: &str = r#"# Extra instructions appended to the system prompt.
 # Useful for project descriptions or recurring context.
 #context = ""
 
+# Only consider tags matching this glob (or a regex with a (?P<version>...)
+# group) when finding the previous release, e.g. in a monorepo.
+#tag_pattern = "cli-v*"
+
 [defaults]
 #model = "claude-opus-4-8"
 #max_tokens = 16384
@@ -43,6 +232,47 @@ const TEMPLATE: &str = r#"# Extra instructions appended to the system prompt.
 #emoji = true
 #verify_links = true
 #match_style = true
+#conventional_commits = true
+# Create the release on --publish-release when the tag has none.
+#create_release = true
+# The forge hosting the repo (github, gitlab, gitea or bitbucket); detected
+# from the remote's host when it names one, as gitlab.com and codeberg.org do.
+#forge = "gitea"
+# GitHub Enterprise Server host; unrecognized hosts are an error until it or
+# forge is set.
+#github_host = "github.example.com"
+#github_api_url = "https://github.example.com/api/v3"
+
+# Restrict which tools the agent may call and which files it may read.
+# When using this table, declare custom tools as [[tools.custom]] instead.
+#[tools]
+#enabled = ["read_file", "list_files", "grep", "git_show", "get_commits"]
+#disabled = ["get_issue"]
+#read_deny = ["secrets/**", "*.pem"]
+#grep_max_matches = 50
+#use_ripgrep = false
+
+# Project-specific tools the agent can call. `command` is run from the repo
+# root without a shell; `{field}` placeholders are filled from the input.
+#[[tools]]
+#name = "cargo_features"
+#description = "List the cargo features declared by a package"
+#command = ["cargo", "metadata", "--no-deps", "--format-version", "1"]
+#timeout = 30
+#max_output = 50000
+#
+#[[tools]]
+#name = "cli_help"
+#description = "Show --help output for a subcommand"
+#input_schema = { type = "object", properties = { subcommand = { type = "string" } }, required = ["subcommand"] }
+#command = ["cargo", "run", "--quiet", "--", "{subcommand}", "--help"]
+
+# Packages in a monorepo, released with `communique generate --package <name>`.
+# Only commits touching `paths` are considered.
+#[packages.cli]
+#paths = ["packages/cli"]
+#changelog = "packages/cli/CHANGELOG.md"
+#tag_pattern = "cli-v*"
 "#;
 
 impl Config {
@@ -51,6 +281,21 @@ impl Config {
         Self::load_from(&path)
     }
 
+    /// The `[packages.<name>]` entry for `--package`.
+    pub fn package(&self, name: &str) -> Result<&PackageConfig> {
+        self.packages.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.packages.keys().map(|k| k.as_str()).collect();
+            crate::error::Error::Config(format!(
+                "unknown package '{name}' (known packages: {})",
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            ))
+        })
+    }
+
     pub fn load_from(path: &Path) -> Result<Option<Config>> {
         if !path.exists() {
             return Ok(None);
@@ -77,6 +322,14 @@ impl Config {
                     "max_tokens must be greater than 0".into(),
                 ));
             }
+            if let Some(url) = &defaults.github_api_url
+                && !url.starts_with("https://")
+                && !url.starts_with("http://")
+            {
+                return Err(crate::error::Error::Config(format!(
+                    "invalid github_api_url '{url}' (expected an http(s) URL)"
+                )));
+            }
             if let Some(repo) = &defaults.repo
                 && (!repo.contains('/') || repo.starts_with('/') || repo.ends_with('/'))
             {
@@ -85,6 +338,77 @@ impl Config {
                 )));
             }
         }
+        if let Some(pattern) = &self.tag_pattern {
+            crate::tags::TagPattern::new(pattern)?;
+        }
+        for (name, package) in &self.packages {
+            if package.paths.is_empty() {
+                return Err(crate::error::Error::Config(format!(
+                    "package '{name}' has no paths"
+                )));
+            }
+            if let Some(pattern) = &package.tag_pattern {
+                crate::tags::TagPattern::new(pattern)?;
+            }
+        }
+        let mut seen = std::collections::HashSet::new();
+        for tool in &self.tools.custom {
+            let name = &tool.name;
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(crate::error::Error::Config(format!(
+                    "invalid tool name '{name}' (use letters, digits, '_' or '-')"
+                )));
+            }
+            if crate::tools::BUILTIN_TOOLS.contains(&name.as_str()) {
+                return Err(crate::error::Error::Config(format!(
+                    "tool '{name}' conflicts with a built-in tool"
+                )));
+            }
+            if !seen.insert(name.as_str()) {
+                return Err(crate::error::Error::Config(format!(
+                    "tool '{name}' is declared more than once"
+                )));
+            }
+            if tool.command.is_empty() {
+                return Err(crate::error::Error::Config(format!(
+                    "tool '{name}' has an empty command"
+                )));
+            }
+            if tool.timeout == Some(0) {
+                return Err(crate::error::Error::Config(format!(
+                    "tool '{name}': timeout must be greater than 0"
+                )));
+            }
+        }
+        let listed = self
+            .tools
+            .enabled
+            .iter()
+            .flatten()
+            .chain(&self.tools.disabled);
+        for name in listed {
+            if !crate::tools::BUILTIN_TOOLS.contains(&name.as_str())
+                && !seen.contains(name.as_str())
+            {
+                return Err(crate::error::Error::Config(format!(
+                    "unknown tool '{name}' in [tools]"
+                )));
+            }
+        }
+        if self
+            .tools
+            .disabled
+            .iter()
+            .any(|d| d == "submit_release_notes")
+        {
+            return Err(crate::error::Error::Config(
+                "submit_release_notes cannot be disabled".into(),
+            ));
+        }
         Ok(())
     }
 
@@ -178,12 +502,177 @@ emoji = false
         assert!(err.to_string().contains("noslash"));
     }
 
+    #[test]
+    fn test_load_custom_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            r#"
+[[tools]]
+name = "cli_help"
+description = "Show --help for a subcommand"
+input_schema = { type = "object", properties = { cmd = { type = "string" } }, required = ["cmd"] }
+command = ["mycli", "{cmd}", "--help"]
+timeout = 5
+
+[[tools]]
+name = "features"
+description = "List features"
+command = ["cargo", "metadata"]
+"#,
+        )
+        .unwrap();
+        let config = Config::load(dir.path()).unwrap().unwrap();
+        let tools = &config.tools.custom;
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].name, "cli_help");
+        assert_eq!(tools[0].command, ["mycli", "{cmd}", "--help"]);
+        assert_eq!(tools[0].timeout, Some(5));
+        assert_eq!(
+            tools[0].input_schema["required"],
+            serde_json::json!(["cmd"])
+        );
+        assert_eq!(tools[1].input_schema["type"], "object");
+        assert!(config.tools.enabled.is_none());
+    }
+
+    #[test]
+    fn test_load_tools_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            r#"
+[tools]
+enabled = ["read_file", "grep", "features"]
+disabled = ["grep"]
+read_deny = ["secrets/**"]
+
+[[tools.custom]]
+name = "features"
+description = "List features"
+command = ["cargo", "metadata"]
+"#,
+        )
+        .unwrap();
+        let config = Config::load(dir.path()).unwrap().unwrap();
+        let tools = &config.tools;
+        assert_eq!(tools.custom.len(), 1);
+        assert_eq!(tools.read_deny, ["secrets/**"]);
+        assert!(tools.is_enabled("read_file"));
+        assert!(tools.is_enabled("features"));
+        assert!(tools.is_enabled("submit_release_notes"));
+        assert!(!tools.is_enabled("grep"));
+        assert!(!tools.is_enabled("get_issue"));
+    }
+
+    #[test]
+    fn test_load_tools_table_reports_bad_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("communique.toml");
+        std::fs::write(&path, "[tools]\nread_denny = [\"secrets/**\"]\n").unwrap();
+        let err = Config::load(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("unknown field `read_denny`"), "{err}");
+
+        std::fs::write(&path, "[tools]\nuse_ripgrep = \"yes\"\n").unwrap();
+        let err = Config::load(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("invalid type"), "{err}");
+        assert!(!err.contains("did not match any variant"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_tools_policy_unknown_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            "[tools]\ndisabled = [\"read_fiel\"]\n",
+        )
+        .unwrap();
+        let err = Config::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("read_fiel"), "err: {err}");
+    }
+
+    #[test]
+    fn test_validate_custom_tool_conflicts_with_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            "[[tools]]\nname = \"read_file\"\ndescription = \"x\"\ncommand = [\"cat\"]\n",
+        )
+        .unwrap();
+        let err = Config::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("built-in"), "err: {err}");
+    }
+
+    #[test]
+    fn test_validate_custom_tool_empty_command() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            "[[tools]]\nname = \"noop\"\ndescription = \"x\"\ncommand = []\n",
+        )
+        .unwrap();
+        let err = Config::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("empty command"), "err: {err}");
+    }
+
+    #[test]
+    fn test_validate_tag_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            "tag_pattern = \"cli-release\"\n",
+        )
+        .unwrap();
+        let err = Config::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("has no version"), "err: {err}");
+
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            "tag_pattern = \"cli-v*\"\n",
+        )
+        .unwrap();
+        let config = Config::load(dir.path()).unwrap().unwrap();
+        assert_eq!(config.tag_pattern.as_deref(), Some("cli-v*"));
+    }
+
+    #[test]
+    fn test_load_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            r#"
+[packages.cli]
+paths = ["packages/cli", "crates/shared"]
+changelog = "packages/cli/CHANGELOG.md"
+tag_pattern = "cli-v*"
+
+[packages.sdk]
+paths = ["packages/sdk"]
+"#,
+        )
+        .unwrap();
+        let config = Config::load(dir.path()).unwrap().unwrap();
+        let cli = &config.packages["cli"];
+        assert_eq!(cli.paths, ["packages/cli", "crates/shared"]);
+        assert_eq!(cli.changelog.as_deref(), Some("packages/cli/CHANGELOG.md"));
+        assert_eq!(cli.tag_pattern.as_deref(), Some("cli-v*"));
+        assert!(config.packages["sdk"].changelog.is_none());
+
+        std::fs::write(
+            dir.path().join("communique.toml"),
+            "[packages.cli]\nchangelog = \"CHANGELOG.md\"\n",
+        )
+        .unwrap();
+        let err = Config::load(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("has no paths"), "err: {err}");
+    }
+
     #[test]
     fn test_validate_valid_defaults() {
         let dir = tempfile::tempdir().unwrap();
         std::fs::write(
             dir.path().join("communique.toml"),
-            "[defaults]\nmax_tokens = 4096\nprovider = \"openai\"\nrepo = \"jdx/communique\"\n",
+            "[defaults]\nmax_tokens = 4096\nprovider = \"openai\"\nrepo = \"jdx/communique\"\nforge = \"forgejo\"\n",
         )
         .unwrap();
         let config = Config::load(dir.path()).unwrap().unwrap();
@@ -191,5 +680,6 @@ emoji = false
         assert_eq!(defaults.max_tokens, Some(4096));
         assert_eq!(defaults.provider, Some(crate::providers::Provider::OpenAI));
         assert_eq!(defaults.repo.as_deref(), Some("jdx/communique"));
+        assert_eq!(defaults.forge, Some(ForgeKind::Gitea));
     }
 }
@@ -0,0 +1,194 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::git::Commit;
+
+static HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()]*)\))?(?P<bang>!)?: +(?P<desc>\S.*)$")
+        .unwrap()
+});
+
+/// Display order for well-known types; anything else sorts after these.
+const TYPE_ORDER: &[&str] = &[
+    "feat", "fix", "perf", "revert", "refactor", "docs", "style", "test", "build", "ci", "chore",
+];
+
+/// A commit subject parsed per the Conventional Commits spec.
+#[derive(Debug, PartialEq)]
+pub struct ConventionalCommit {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    /// Text of a `BREAKING CHANGE:` footer, if any.
+    pub breaking_note: Option<String>,
+    pub description: String,
+}
+
+pub fn parse(subject: &str, body: &str) -> Option<ConventionalCommit> {
+    let caps = HEADER_RE.captures(subject.trim())?;
+    let breaking_note = body.lines().find_map(|line| {
+        line.strip_prefix("BREAKING CHANGE:")
+            .or_else(|| line.strip_prefix("BREAKING-CHANGE:"))
+            .map(|note| note.trim().to_string())
+    });
+    Some(ConventionalCommit {
+        kind: caps["type"].to_lowercase(),
+        scope: caps
+            .name("scope")
+            .map(|s| s.as_str().trim().to_string())
+            .filter(|s| !s.is_empty()),
+        breaking: caps.name("bang").is_some() || breaking_note.is_some(),
+        breaking_note,
+        description: caps["desc"].trim().to_string(),
+    })
+}
+
+/// Whether at least half of the commits follow the convention, so repos that
+/// only occasionally write `fix:` keep the plain log.
+pub fn uses_convention(commits: &[Commit]) -> bool {
+    let parsed = commits
+        .iter()
+        .filter(|c| parse(&c.subject, &c.body).is_some())
+        .count();
+    !commits.is_empty() && parsed * 2 >= commits.len()
+}
+
+/// Render commits as a markdown table grouped by type, preceded by an explicit
+/// list of breaking changes. Commits that don't follow the convention are
+/// listed last with type `-`.
+pub fn commit_table(commits: &[Commit]) -> String {
+    let mut rows: Vec<(&Commit, Option<ConventionalCommit>)> = commits
+        .iter()
+        .map(|c| (c, parse(&c.subject, &c.body)))
+        .collect();
+    rows.sort_by_key(|(_, cc)| match cc {
+        Some(cc) => (
+            TYPE_ORDER
+                .iter()
+                .position(|t| *t == cc.kind)
+                .unwrap_or(TYPE_ORDER.len()),
+            cc.kind.clone(),
+        ),
+        None => (TYPE_ORDER.len() + 1, String::new()),
+    });
+
+    let mut out = String::new();
+    let breaking: Vec<String> = rows
+        .iter()
+        .filter_map(|(c, cc)| {
+            let cc = cc.as_ref().filter(|cc| cc.breaking)?;
+            Some(match &cc.breaking_note {
+                Some(note) => format!("- `{}` {} — {note}", c.sha, c.subject),
+                None => format!("- `{}` {}", c.sha, c.subject),
+            })
+        })
+        .collect();
+    if !breaking.is_empty() {
+        out.push_str("### ⚠️ Breaking changes\n");
+        out.push_str(&breaking.join("\n"));
+        out.push_str("\n\n");
+    }
+
+    out.push_str("| Commit | Type | Scope | Description | Breaking |\n");
+    out.push_str("|--------|------|-------|-------------|----------|\n");
+    for (c, cc) in &rows {
+        let row = match cc {
+            Some(cc) => format!(
+                "| {} | {} | {} | {} | {} |\n",
+                c.sha,
+                cc.kind,
+                cc.scope.as_deref().unwrap_or(""),
+                escape_cell(&cc.description),
+                if cc.breaking { "**yes**" } else { "" }
+            ),
+            None => format!("| {} | - | | {} | |\n", c.sha, escape_cell(&c.subject)),
+        };
+        out.push_str(&row);
+    }
+    out.trim_end().to_string()
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str, subject: &str, body: &str) -> Commit {
+        Commit {
+            sha: sha.into(),
+            subject: subject.into(),
+            body: body.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let cc = parse("feat(cli)!: drop --legacy flag (#12)", "").unwrap();
+        assert_eq!(cc.kind, "feat");
+        assert_eq!(cc.scope.as_deref(), Some("cli"));
+        assert!(cc.breaking);
+        assert_eq!(cc.description, "drop --legacy flag (#12)");
+
+        let cc = parse(
+            "fix: handle empty config",
+            "Details.\n\nBREAKING CHANGE: config must now be a table",
+        )
+        .unwrap();
+        assert!(cc.breaking);
+        assert_eq!(
+            cc.breaking_note.as_deref(),
+            Some("config must now be a table")
+        );
+
+        assert!(parse("Merge branch 'main'", "").is_none());
+        assert!(parse("Update README.md", "").is_none());
+    }
+
+    #[test]
+    fn test_uses_convention() {
+        let conventional = [
+            commit("a", "feat: one", ""),
+            commit("b", "Bump version", ""),
+        ];
+        assert!(uses_convention(&conventional));
+
+        let plain = [
+            commit("a", "Add one", ""),
+            commit("b", "Bump version", ""),
+            commit("c", "fix: typo", ""),
+        ];
+        assert!(!uses_convention(&plain));
+        assert!(!uses_convention(&[]));
+    }
+
+    #[test]
+    fn test_commit_table_groups_and_flags_breaking() {
+        let commits = [
+            commit("c1", "chore: bump deps", ""),
+            commit("c2", "fix(api): null | undefined", ""),
+            commit("c3", "Tidy up", ""),
+            commit(
+                "c4",
+                "feat: add plugins",
+                "BREAKING CHANGE: plugins dir moved",
+            ),
+        ];
+        let table = commit_table(&commits);
+        assert_eq!(
+            table,
+            "### ⚠️ Breaking changes\n\
+             - `c4` feat: add plugins — plugins dir moved\n\
+             \n\
+             | Commit | Type | Scope | Description | Breaking |\n\
+             |--------|------|-------|-------------|----------|\n\
+             | c4 | feat |  | add plugins | **yes** |\n\
+             | c2 | fix | api | null \\| undefined |  |\n\
+             | c1 | chore |  | bump deps |  |\n\
+             | c3 | - | | Tidy up | |"
+        );
+    }
+}
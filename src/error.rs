@@ -11,6 +11,12 @@ pub enum Error {
     #[error("GitHub API error: {0}")]
     GitHub(String),
 
+    #[error("GitLab API error: {0}")]
+    GitLab(String),
+
+    #[error("Gitea API error: {0}")]
+    Gitea(String),
+
     #[error("LLM API error: {0}")]
     Llm(String),
 
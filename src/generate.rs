@@ -5,15 +5,25 @@ use clx::progress::{ProgressJob, ProgressJobBuilder, ProgressStatus};
 use log::info;
 
 use crate::config::Defaults;
+use crate::forge::{ForgeClient, ForgeKind, Remote};
 use crate::llm::LlmClient;
 use crate::output::ParsedOutput;
 use crate::providers::{self, Provider};
-use crate::{agent, config, git, github, prompt, tools};
+use crate::tags::{self, Since, TagPattern};
+use crate::{
+    agent, changelog, config, conventional, git, gitea, github, gitlab, offline, prompt, tools,
+};
 
 pub struct GenerateOptions {
     pub tag: String,
     pub prev_tag: Option<String>,
-    pub github_release: bool,
+    pub publish_release: bool,
+    /// Create the release when the tag has none; implies `publish_release`.
+    pub create_release: bool,
+    pub draft: bool,
+    /// Also inferred from the tag's semver prerelease suffix.
+    pub prerelease: bool,
+    pub make_latest: bool,
     pub changelog: bool,
     pub concise: bool,
     pub dry_run: bool,
@@ -23,20 +33,34 @@ pub struct GenerateOptions {
     pub provider: Option<Provider>,
     pub base_url: Option<String>,
     pub output: Option<PathBuf>,
+    pub no_llm: bool,
+    pub package: Option<String>,
+    pub since: Since,
+    pub no_ancestry_warning: bool,
     pub config: Option<PathBuf>,
 }
 
 struct Context {
     repo_root: PathBuf,
-    #[allow(dead_code)]
-    owner_repo: String,
+    remote: Remote,
     tag: String,
     prev_tag: String,
-    client: Box<dyn LlmClient>,
+    /// Unset with `--no-llm`.
+    client: Option<Box<dyn LlmClient>>,
     defaults: Defaults,
     system_extra: Option<String>,
     context: Option<String>,
-    github_client: Option<github::GitHubClient>,
+    forge: Option<ForgeClient>,
+    tools_config: config::ToolsConfig,
+    tag_pattern: Option<TagPattern>,
+    /// Set with `--package`; scopes the log and changelog to one package.
+    package: Option<Package>,
+}
+
+struct Package {
+    name: String,
+    paths: Vec<String>,
+    changelog: Option<String>,
 }
 
 impl Context {
@@ -44,6 +68,33 @@ impl Context {
         self.tag == "HEAD"
     }
 
+    /// The tag's version as written in CHANGELOG.md headers: `cli-v1.4.0`
+    /// becomes `1.4.0` under a `cli-v*` tag pattern, but only when the
+    /// package has a changelog of its own. A shared changelog keeps the full
+    /// tag so packages releasing the same version don't overwrite each other.
+    fn changelog_version(&self) -> &str {
+        let own_changelog = self.package.as_ref().is_some_and(|p| p.changelog.is_some());
+        self.tag_pattern
+            .as_ref()
+            .filter(|_| own_changelog)
+            .and_then(|p| p.version(&self.tag))
+            .unwrap_or(&self.tag)
+    }
+
+    /// Pathspecs limiting the git log; empty releases the whole repo.
+    fn paths(&self) -> &[String] {
+        self.package.as_ref().map_or(&[], |p| &p.paths)
+    }
+
+    fn changelog_path(&self) -> PathBuf {
+        let changelog = self
+            .package
+            .as_ref()
+            .and_then(|p| p.changelog.as_deref())
+            .unwrap_or("CHANGELOG.md");
+        self.repo_root.join(changelog)
+    }
+
     fn display_tag(&self) -> &str {
         if self.is_unreleased_head() {
             "Unreleased"
@@ -53,10 +104,26 @@ impl Context {
     }
 }
 
+impl GenerateOptions {
+    fn publishes(&self) -> bool {
+        self.publish_release || self.create_release
+    }
+}
+
 fn validate_generate_options(opts: &GenerateOptions) -> miette::Result<()> {
-    if opts.github_release && opts.tag == "HEAD" {
+    if opts.publishes() && opts.tag == "HEAD" {
+        let flag = if opts.create_release {
+            "--create-release"
+        } else {
+            "--publish-release"
+        };
+        return Err(miette::miette!(
+            "{flag} cannot be used with HEAD because HEAD is an unreleased changelog target. Use --changelog, or generate notes for a real tag."
+        ));
+    }
+    if (opts.draft || opts.prerelease || opts.make_latest) && !opts.publishes() {
         return Err(miette::miette!(
-            "--github-release cannot be used with HEAD because HEAD is an unreleased changelog target. Use --changelog, or generate notes for a real tag."
+            "--draft, --prerelease and --make-latest apply to a created release; add --create-release"
         ));
     }
 
@@ -110,15 +177,19 @@ pub async fn run(opts: GenerateOptions) -> miette::Result<()> {
 
     let ctx = gather_context(&opts, &job).await?;
     let include_changelog = opts.changelog || opts.concise;
-    let include_release_notes = opts.github_release || !opts.concise;
-    let mut parsed = generate_notes(
-        &ctx,
-        opts.dry_run,
-        include_release_notes,
-        include_changelog,
-        &job,
-    )
-    .await?;
+    let include_release_notes = opts.publishes() || !opts.concise;
+    let mut parsed = if opts.no_llm {
+        generate_offline_notes(&ctx, &job).await?
+    } else {
+        generate_notes(
+            &ctx,
+            opts.dry_run,
+            include_release_notes,
+            include_changelog,
+            &job,
+        )
+        .await?
+    };
 
     // Normalize release title to "label: description" format.
     // The LLM may include the tag with a different separator (e.g. "v1.0.0 (title)"
@@ -138,13 +209,15 @@ pub async fn run(opts: GenerateOptions) -> miette::Result<()> {
     job.prop("message", "Done");
     clx::progress::flush();
 
-    let u = &parsed.usage;
-    eprintln!(
-        "Tokens: {} input + {} output = {} total",
-        u.input_tokens,
-        u.output_tokens,
-        u.input_tokens + u.output_tokens
-    );
+    if !opts.no_llm {
+        let u = &parsed.usage;
+        eprintln!(
+            "Tokens: {} input + {} output = {} total",
+            u.input_tokens,
+            u.output_tokens,
+            u.input_tokens + u.output_tokens
+        );
+    }
 
     let text = if opts.concise {
         parsed.changelog.clone()
@@ -162,67 +235,65 @@ pub async fn run(opts: GenerateOptions) -> miette::Result<()> {
 }
 
 async fn gather_context(opts: &GenerateOptions, job: &Arc<ProgressJob>) -> miette::Result<Context> {
-    let github_token = std::env::var("GITHUB_TOKEN").ok();
-
-    if opts.github_release && github_token.is_none() {
-        Err(crate::error::Error::GitHub(
-            "GITHUB_TOKEN is required for --github-release".into(),
-        ))?;
-    }
-
     let repo_root = git::repo_root()?;
     info!("repo root: {}", repo_root.display());
 
-    let config = match &opts.config {
+    let mut config = match &opts.config {
         Some(path) => config::Config::load_from(path)?,
         None => config::Config::load(&repo_root)?,
     }
     .unwrap_or_default();
-    let defaults = config.defaults.unwrap_or_default();
+    let defaults = config.defaults.take().unwrap_or_default();
+
+    let remote = resolve_remote(&repo_root, opts.repo.as_deref(), &defaults)?;
+    info!("repo: {} on {} ({})", remote.path, remote.host, remote.kind);
+    let forge = forge_client(&remote, &defaults)?;
+    if opts.publishes() && forge.is_none() {
+        let message = match remote.kind {
+            ForgeKind::GitHub => "GITHUB_TOKEN is required for --publish-release".to_string(),
+            ForgeKind::GitLab => "GITLAB_TOKEN is required for --publish-release".to_string(),
+            ForgeKind::Gitea => "GITEA_TOKEN is required for --publish-release".to_string(),
+            kind => format!("--publish-release is not supported on {kind}"),
+        };
+        Err(crate::error::Error::Config(message))?;
+    }
 
-    let model = opts
-        .model
-        .clone()
-        .or(defaults.model.clone())
-        .unwrap_or_else(|| "claude-opus-4-8".into());
-    let max_tokens = opts
-        .max_tokens
-        .or(defaults.max_tokens)
-        .unwrap_or(config::DEFAULT_MAX_TOKENS);
-    let owner_repo = match opts.repo.clone().or(defaults.repo.clone()) {
-        Some(r) => r,
-        None => git::detect_remote(&repo_root)?,
+    let client = if opts.no_llm {
+        info!("--no-llm: building notes from commits and PRs only");
+        None
+    } else {
+        Some(build_llm_client(
+            opts.model.clone(),
+            opts.max_tokens,
+            opts.provider.clone(),
+            opts.base_url.clone(),
+            &defaults,
+        )?)
     };
-    info!("repo: {owner_repo}");
-
-    // Determine provider
-    let provider = opts
-        .provider
-        .clone()
-        .or(defaults.provider.clone())
-        .unwrap_or_else(|| providers::detect_provider(&model));
-    info!("provider: {provider:?}, model: {model}");
 
-    // Resolve API key based on provider
-    let api_key = match &provider {
-        Provider::Anthropic => std::env::var("ANTHROPIC_API_KEY")
-            .map_err(|_| crate::error::Error::Llm("ANTHROPIC_API_KEY not set".into()))?,
-        Provider::OpenAI => std::env::var("OPENAI_API_KEY")
-            .or_else(|_| std::env::var("LLM_API_KEY"))
-            .unwrap_or_default(),
+    let package = match &opts.package {
+        Some(name) => {
+            let pkg = config.package(name)?;
+            info!("package: {name} ({})", pkg.paths.join(", "));
+            Some((name.clone(), pkg.clone()))
+        }
+        None => None,
     };
-
-    let base_url = opts
-        .base_url
-        .clone()
-        .or(defaults.base_url.clone())
-        .filter(|u| !u.is_empty());
-
-    let client = providers::build_client(&provider, api_key, model, max_tokens, base_url);
-
+    let tag_pattern = package
+        .as_ref()
+        .and_then(|(_, pkg)| pkg.tag_pattern.as_deref())
+        .or(config.tag_pattern.as_deref())
+        .map(TagPattern::new)
+        .transpose()?;
     let prev_tag = match &opts.prev_tag {
         Some(t) => t.clone(),
-        None => git::previous_tag(&repo_root, &opts.tag)?,
+        None => git::previous_tag(
+            &repo_root,
+            &opts.tag,
+            tag_pattern.as_ref(),
+            opts.since,
+            !opts.no_ancestry_warning,
+        )?,
     };
     info!("range: {prev_tag}..{}", opts.tag);
 
@@ -231,24 +302,124 @@ async fn gather_context(opts: &GenerateOptions, job: &Arc<ProgressJob>) -> miett
         &format!("Reading git log {prev_tag}..{}...", opts.tag),
     );
 
-    let github_client = github_token
-        .as_ref()
-        .map(|token| github::GitHubClient::new(token.clone(), &owner_repo))
-        .transpose()?;
-
     Ok(Context {
         repo_root,
-        owner_repo,
+        remote,
         tag: opts.tag.clone(),
         prev_tag,
         client,
         defaults,
         system_extra: config.system_extra,
         context: config.context,
-        github_client,
+        forge,
+        tools_config: config.tools,
+        tag_pattern,
+        package: package.map(|(name, pkg)| Package {
+            name,
+            paths: pkg.paths,
+            changelog: pkg.changelog,
+        }),
     })
 }
 
+/// The repository from `--repo`, `defaults.repo` or the origin remote, with
+/// `defaults.forge` naming the forge and `defaults.github_host` pointing
+/// GitHub repos at an Enterprise Server.
+pub(crate) fn resolve_remote(
+    repo_root: &Path,
+    repo: Option<&str>,
+    defaults: &Defaults,
+) -> miette::Result<Remote> {
+    let origin = git::detect_remote(repo_root, defaults.forge, defaults.github_host.as_deref());
+    // An origin on an unrecognized host isn't a reason to assume github.com.
+    if let Err(err @ crate::error::Error::Config(_)) = origin {
+        return Err(err.into());
+    }
+    let mut remote = match repo.or(defaults.repo.as_deref()) {
+        Some(r) => Remote::from_repo_arg(
+            r,
+            origin.as_ref().ok(),
+            defaults.forge,
+            defaults.github_host.as_deref(),
+        )?,
+        None => origin?,
+    };
+    if let Some(kind) = defaults.forge {
+        remote.kind = kind;
+    }
+    if let Some(host) = &defaults.github_host
+        && remote.kind == ForgeKind::GitHub
+    {
+        remote.host = host.clone();
+    }
+    Ok(remote)
+}
+
+/// An API client for the remote's forge, when it has one and its token
+/// (`GITHUB_TOKEN`, `GITLAB_TOKEN` or `GITEA_TOKEN`) is set.
+pub(crate) fn forge_client(
+    remote: &Remote,
+    defaults: &Defaults,
+) -> miette::Result<Option<ForgeClient>> {
+    let token = |name: &str| std::env::var(name).ok().filter(|t| !t.is_empty());
+    let client = match remote.kind {
+        ForgeKind::GitHub => token("GITHUB_TOKEN")
+            .map(|t| {
+                github::GitHubClient::for_remote(t, remote, defaults.github_api_url.as_deref())
+            })
+            .transpose()?
+            .map(ForgeClient::GitHub),
+        ForgeKind::GitLab => token("GITLAB_TOKEN")
+            .map(|t| gitlab::GitLabClient::for_remote(t, remote, None))
+            .transpose()?
+            .map(ForgeClient::GitLab),
+        ForgeKind::Gitea => token("GITEA_TOKEN")
+            .map(|t| gitea::GiteaClient::for_remote(t, remote))
+            .transpose()?
+            .map(ForgeClient::Gitea),
+        ForgeKind::Bitbucket => None,
+    };
+    Ok(client)
+}
+
+/// The LLM client for the given flags, falling back to `[defaults]`.
+pub(crate) fn build_llm_client(
+    model: Option<String>,
+    max_tokens: Option<u32>,
+    provider: Option<Provider>,
+    base_url: Option<String>,
+    defaults: &Defaults,
+) -> miette::Result<Box<dyn LlmClient>> {
+    let model = model
+        .or(defaults.model.clone())
+        .unwrap_or_else(|| "claude-opus-4-8".into());
+    let max_tokens = max_tokens
+        .or(defaults.max_tokens)
+        .unwrap_or(config::DEFAULT_MAX_TOKENS);
+    // Determine provider
+    let provider = provider
+        .or(defaults.provider.clone())
+        .unwrap_or_else(|| providers::detect_provider(&model));
+    info!("provider: {provider:?}, model: {model}");
+
+    // Resolve API key based on provider
+    let api_key = match &provider {
+        Provider::Anthropic => std::env::var("ANTHROPIC_API_KEY")
+            .map_err(|_| crate::error::Error::Llm("ANTHROPIC_API_KEY not set".into()))?,
+        Provider::OpenAI => std::env::var("OPENAI_API_KEY")
+            .or_else(|_| std::env::var("LLM_API_KEY"))
+            .unwrap_or_default(),
+    };
+
+    let base_url = base_url
+        .or(defaults.base_url.clone())
+        .filter(|u| !u.is_empty());
+
+    Ok(providers::build_client(
+        &provider, api_key, model, max_tokens, base_url,
+    ))
+}
+
 async fn generate_notes(
     ctx: &Context,
     dry_run: bool,
@@ -256,36 +427,73 @@ async fn generate_notes(
     include_changelog: bool,
     job: &Arc<ProgressJob>,
 ) -> miette::Result<ParsedOutput> {
-    let git_log = git::log_between(&ctx.repo_root, &ctx.prev_tag, &ctx.tag)?;
-    let pr_numbers = git::extract_pr_numbers(&git_log);
+    let git_log = git::log_between(&ctx.repo_root, &ctx.prev_tag, &ctx.tag, ctx.paths())?;
+    let commits = git::commits_between(&ctx.repo_root, &ctx.prev_tag, &ctx.tag, ctx.paths())?;
+    // Changes reverted within the range never shipped; keep them out of the
+    // log so the model can't announce them.
+    let (commits, reverts) = git::pair_reverts(commits);
+    let git_log = if reverts.is_empty() {
+        git_log
+    } else {
+        info!("dropping {} reverted commit pairs", reverts.len());
+        git_log
+            .lines()
+            .filter(|line| {
+                let sha = line.split_once(' ').map_or(*line, |(sha, _)| sha);
+                commits.iter().any(|c| c.sha == sha)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let pr_numbers = git::extract_pr_numbers(&commits);
+    // Group the log by Conventional Commit type when the repo opts in, or when
+    // unset and most commits in the range follow the convention.
+    let commit_table = (ctx.defaults.conventional_commits == Some(true)
+        || ctx.defaults.conventional_commits.is_none() && conventional::uses_convention(&commits))
+    .then(|| conventional::commit_table(&commits));
     info!(
         "found {} commits, {} PRs",
         git_log.lines().count(),
         pr_numbers.len()
     );
+    let backports = git::backports(
+        &ctx.repo_root,
+        &ctx.prev_tag,
+        &ctx.tag,
+        ctx.paths(),
+        ctx.tag_pattern.as_ref(),
+    )
+    .unwrap_or_else(|e| {
+        info!("failed to detect backports: {e}");
+        vec![]
+    });
+    if !backports.is_empty() {
+        info!("found {} cherry-picked commits", backports.len());
+    }
 
     job.prop("message", "Fetching existing release context...");
     let changelog_entry = if ctx.is_unreleased_head() {
-        read_unreleased_section(&ctx.repo_root)?
+        changelog::read_unreleased_section(&ctx.changelog_path())?
     } else {
-        read_changelog_entry(&ctx.repo_root, &ctx.tag)
+        changelog::read_entry(&ctx.changelog_path(), &ctx.tag)
+            .or_else(|| changelog::read_entry(&ctx.changelog_path(), ctx.changelog_version()))
     };
 
     // Fetch existing release and recent releases in parallel
     let match_style = ctx.defaults.match_style.unwrap_or(true);
-    let (existing_release, recent_releases) = if let Some(gh) = &ctx.github_client {
+    let (existing_release, recent_releases) = if let Some(forge) = &ctx.forge {
         let existing_fut = async {
             if ctx.is_unreleased_head() {
                 Ok(None)
             } else {
-                gh.get_release_by_tag(&ctx.tag).await
+                forge.get_release_by_tag(&ctx.tag).await
             }
         };
         let recent_fut = async {
             if !match_style {
                 return vec![];
             }
-            match gh.list_recent_releases(3).await {
+            match forge.list_recent_releases(3).await {
                 Ok(releases) => releases
                     .into_iter()
                     .filter(|r| r.tag_name != ctx.tag)
@@ -325,32 +533,47 @@ async fn generate_notes(
     let user_msg = prompt::user_prompt(&prompt::UserPromptContext {
         tag: &ctx.tag,
         prev_tag: &ctx.prev_tag,
-        owner_repo: &ctx.owner_repo,
+        remote: &ctx.remote,
         git_log: &git_log,
+        commit_table: commit_table.as_deref(),
         pr_numbers: &pr_numbers,
         is_unreleased_head: ctx.is_unreleased_head(),
         changelog_entry: changelog_entry.as_deref(),
         existing_release: existing_release.as_deref(),
         context: ctx.context.as_deref(),
         recent_releases: &recent_releases,
+        package: ctx.package.as_ref().map(|p| p.name.as_str()),
+        package_paths: ctx.paths(),
+        backports: &backports,
+        reverts: &reverts,
     });
 
     job.prop("message", "Generating release notes...");
     let tool_defs = tools::all_definitions(
-        ctx.github_client.is_some(),
+        ctx.forge.as_ref().map(ForgeClient::kind),
         include_release_notes,
         include_changelog,
+        &ctx.tools_config,
     );
 
     let verify_links = !dry_run && ctx.defaults.verify_links.unwrap_or(true);
+    let changelog_path = ctx.changelog_path();
 
     agent::run(agent::AgentContext {
-        client: &*ctx.client,
+        client: ctx
+            .client
+            .as_deref()
+            .expect("generate_notes requires an LLM client"),
         system: &system,
         user_message: &user_msg,
         tool_defs,
-        repo_root: &ctx.repo_root,
-        github: ctx.github_client.as_ref(),
+        tools: tools::ToolContext {
+            config: &ctx.tools_config,
+            tag: Some(&ctx.tag),
+            prev_tag: Some(&ctx.prev_tag),
+            changelog: Some(&changelog_path),
+            ..tools::ToolContext::new(&ctx.repo_root, ctx.forge.as_ref())
+        },
         verify_links,
         require_release_notes: include_release_notes,
         require_changelog: include_changelog,
@@ -360,33 +583,132 @@ async fn generate_notes(
     .map_err(Into::into)
 }
 
+/// Build notes from commit subjects and, when the forge's API is available,
+/// PR titles and labels, without calling a model.
+async fn generate_offline_notes(
+    ctx: &Context,
+    job: &Arc<ProgressJob>,
+) -> miette::Result<ParsedOutput> {
+    let commits = git::commits_between(&ctx.repo_root, &ctx.prev_tag, &ctx.tag, ctx.paths())?;
+    let (commits, reverts) = git::pair_reverts(commits);
+    info!(
+        "found {} commits ({} reverted pairs dropped)",
+        commits.len(),
+        reverts.len()
+    );
+
+    let mut prs = std::collections::HashMap::new();
+    if let Some(forge) = &ctx.forge {
+        job.prop("message", "Fetching pull requests...");
+        let mut numbers: Vec<u64> = commits
+            .iter()
+            .filter_map(offline::commit_pr_number)
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        let results =
+            futures_util::future::join_all(numbers.iter().map(|n| forge.get_pr(*n))).await;
+        for (number, result) in numbers.into_iter().zip(results) {
+            match result {
+                Ok(pr) => {
+                    prs.insert(number, pr);
+                }
+                Err(e) => info!("failed to fetch PR #{number}: {e}"),
+            }
+        }
+    }
+
+    // A merge commit whose PR we know stands for the whole PR; listing the
+    // commits it brought in too would show each change twice.
+    let mut merged = std::collections::HashSet::new();
+    for commit in &commits {
+        if offline::commit_pr_number(commit).is_some_and(|n| prs.contains_key(&n)) {
+            merged.extend(git::merged_commits(&ctx.repo_root, &commit.sha)?);
+        }
+    }
+    let commits: Vec<git::Commit> = commits
+        .into_iter()
+        .filter(|c| !merged.contains(&c.sha))
+        .collect();
+
+    Ok(offline::build(
+        &commits,
+        &prs,
+        &offline::Release {
+            remote: &ctx.remote,
+            tag: &ctx.tag,
+            prev_tag: &ctx.prev_tag,
+        },
+    ))
+}
+
 async fn publish(
     opts: &GenerateOptions,
     ctx: &Context,
     parsed: &ParsedOutput,
     job: &Arc<ProgressJob>,
 ) -> miette::Result<()> {
-    if opts.github_release && !opts.dry_run {
-        job.prop(
-            "message",
-            &format!("Updating GitHub release for {}...", ctx.tag),
-        );
-        let gh = ctx.github_client.as_ref().unwrap();
-        match gh.get_release_by_tag(&ctx.tag).await? {
+    if opts.publishes() && !opts.dry_run {
+        job.prop("message", &format!("Updating release for {}...", ctx.tag));
+        let forge = ctx.forge.as_ref().unwrap();
+        let create = opts.create_release || ctx.defaults.create_release == Some(true);
+        match forge.get_release_by_tag(&ctx.tag).await? {
             Some(release) => {
-                gh.update_release(
-                    release.id,
-                    &release.tag_name,
-                    Some(&parsed.release_title),
-                    Some(&parsed.release_body),
-                )
-                .await?;
+                forge
+                    .update_release(
+                        &release,
+                        Some(&parsed.release_title),
+                        Some(&parsed.release_body),
+                    )
+                    .await?;
+
+                // The forges' update endpoints would happily flip these, but
+                // turning a published release back into a draft is rarely
+                // what a rerun means.
+                let ignored: Vec<&str> = [
+                    (opts.draft, "--draft"),
+                    (opts.prerelease, "--prerelease"),
+                    (opts.make_latest, "--make-latest"),
+                ]
+                .into_iter()
+                .filter_map(|(set, flag)| set.then_some(flag))
+                .collect();
+                if !ignored.is_empty() {
+                    job.set_status(ProgressStatus::Warn);
+                    job.prop(
+                        "message",
+                        &format!(
+                            "Updated the existing release for {}; {} only apply when creating a release",
+                            ctx.tag,
+                            ignored.join(", ")
+                        ),
+                    );
+                }
+            }
+            None if create => {
+                job.prop("message", &format!("Creating release for {}...", ctx.tag));
+                let prerelease = opts.prerelease
+                    || tags::tag_version(&ctx.tag, ctx.tag_pattern.as_ref())
+                        .is_some_and(|v| v.is_prerelease());
+                forge
+                    .create_release(&github::NewRelease {
+                        tag: &ctx.tag,
+                        title: &parsed.release_title,
+                        body: &parsed.release_body,
+                        draft: opts.draft,
+                        prerelease,
+                        make_latest: opts.make_latest,
+                    })
+                    .await?;
             }
             None => {
                 job.set_status(ProgressStatus::Warn);
                 job.prop(
                     "message",
-                    &format!("No GitHub release found for {} — skipping update", ctx.tag),
+                    &format!(
+                        "No release found for {} — skipping update (pass --create-release to create it)",
+                        ctx.tag
+                    ),
                 );
             }
         }
@@ -395,6 +717,79 @@ async fn publish(
     Ok(())
 }
 
+/// Returns today's date as YYYY-MM-DD using std only.
+fn today_iso() -> String {
+    // Use UNIX_EPOCH + SystemTime to get days, then Hinnant civil_from_days
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let days = (secs / 86400) as i64;
+
+    // Hinnant's civil_from_days (epoch = 1970-01-01 = day 0)
+    let z = days + 719468;
+    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = (yoe as i64) + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+async fn update_changelog(
+    ctx: &Context,
+    parsed: &ParsedOutput,
+    dry_run: bool,
+    job: &Arc<ProgressJob>,
+) -> miette::Result<()> {
+    job.prop("message", "Updating CHANGELOG.md...");
+
+    let changelog_path = ctx.changelog_path();
+    if ctx.is_unreleased_head() {
+        debug_assert_eq!(ctx.tag, "HEAD");
+        let updated = match std::fs::read_to_string(&changelog_path) {
+            Ok(existing) => changelog::replace_unreleased_section(&existing, &parsed.changelog)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                changelog::new_unreleased(&parsed.changelog)
+            }
+            Err(err) => return Err(crate::error::Error::Io(err).into()),
+        };
+
+        if !dry_run {
+            xx::file::write(&changelog_path, updated)?;
+            info!("wrote {}", changelog_path.display());
+        }
+
+        return Ok(());
+    }
+
+    let existing = xx::file::read_to_string(&changelog_path)
+        .unwrap_or_else(|_| "# Changelog\n\n## [Unreleased]\n".to_string());
+
+    let date = today_iso();
+    let release_url = ctx.remote.release_url(&ctx.tag);
+
+    let content = changelog::upsert_version(
+        &existing,
+        &ctx.tag,
+        ctx.changelog_version(),
+        &date,
+        &release_url,
+        &parsed.changelog,
+    )?;
+    if !dry_run {
+        xx::file::write(&changelog_path, content)?;
+        info!("wrote {}", changelog_path.display());
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -404,14 +799,18 @@ mod tests {
         MockLlmClient, TempRepo, fake_usage, fake_usage_with, submit_tool_call,
     };
     use serde_json::json;
-    use wiremock::matchers::{header, method, path};
+    use wiremock::matchers::{body_partial_json, header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     fn test_opts(tag: &str) -> GenerateOptions {
         GenerateOptions {
             tag: tag.into(),
             prev_tag: None,
-            github_release: false,
+            publish_release: false,
+            create_release: false,
+            draft: false,
+            prerelease: false,
+            make_latest: false,
             changelog: false,
             concise: false,
             dry_run: false,
@@ -421,6 +820,10 @@ mod tests {
             provider: None,
             base_url: None,
             output: None,
+            no_llm: false,
+            package: None,
+            since: Since::Auto,
+            no_ancestry_warning: false,
             config: None,
         }
     }
@@ -428,14 +831,17 @@ mod tests {
     fn test_context(repo_root: PathBuf, tag: &str, prev_tag: &str) -> Context {
         Context {
             repo_root,
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: tag.into(),
             prev_tag: prev_tag.into(),
-            client: Box::new(MockLlmClient::new(vec![])),
+            client: Some(Box::new(MockLlmClient::new(vec![]))),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         }
     }
 
@@ -449,18 +855,32 @@ mod tests {
     }
 
     #[test]
-    fn test_validate_generate_options_rejects_head_github_release() {
+    fn test_validate_generate_options_rejects_head_publish_release() {
         let opts = GenerateOptions {
-            github_release: true,
+            publish_release: true,
             ..test_opts("HEAD")
         };
 
         let err = validate_generate_options(&opts).unwrap_err().to_string();
         assert!(err.contains(
-            "--github-release cannot be used with HEAD because HEAD is an unreleased changelog target. Use --changelog, or generate notes for a real tag."
+            "--publish-release cannot be used with HEAD because HEAD is an unreleased changelog target. Use --changelog, or generate notes for a real tag."
         ));
     }
 
+    #[test]
+    fn test_validate_generate_options_names_create_release_with_head() {
+        let opts = GenerateOptions {
+            create_release: true,
+            ..test_opts("HEAD")
+        };
+
+        let err = validate_generate_options(&opts).unwrap_err().to_string();
+        assert!(
+            err.starts_with("--create-release cannot be used with HEAD"),
+            "err: {err}"
+        );
+    }
+
     #[test]
     fn test_validate_generate_options_allows_head_changelog() {
         let opts = GenerateOptions {
@@ -471,10 +891,27 @@ mod tests {
         validate_generate_options(&opts).unwrap();
     }
 
+    #[test]
+    fn test_validate_generate_options_release_flags_need_publishing() {
+        let opts = GenerateOptions {
+            draft: true,
+            ..test_opts("v1.0.0")
+        };
+        let err = validate_generate_options(&opts).unwrap_err().to_string();
+        assert!(err.contains("add --create-release"), "err: {err}");
+
+        let opts = GenerateOptions {
+            create_release: true,
+            draft: true,
+            ..test_opts("v1.0.0")
+        };
+        validate_generate_options(&opts).unwrap();
+    }
+
     #[tokio::test]
-    async fn test_run_rejects_head_github_release_before_context() {
+    async fn test_run_rejects_head_publish_release_before_context() {
         let opts = GenerateOptions {
-            github_release: true,
+            publish_release: true,
             config: Some(PathBuf::from(
                 "/tmp/communique-missing-config-for-validation.toml",
             )),
@@ -483,10 +920,68 @@ mod tests {
 
         let err = run(opts).await.unwrap_err().to_string();
         assert!(err.contains(
-            "--github-release cannot be used with HEAD because HEAD is an unreleased changelog target. Use --changelog, or generate notes for a real tag."
+            "--publish-release cannot be used with HEAD because HEAD is an unreleased changelog target. Use --changelog, or generate notes for a real tag."
         ));
     }
 
+    #[test]
+    fn test_resolve_remote_forge_override() {
+        let repo = TempRepo::new();
+        repo.git(&[
+            "remote",
+            "add",
+            "origin",
+            "git@git.example.org:team/app.git",
+        ]);
+        let err = resolve_remote(repo.path(), None, &Defaults::default()).unwrap_err();
+        assert!(err.to_string().contains("set defaults.forge"), "{err}");
+        let err = resolve_remote(repo.path(), Some("team/other"), &Defaults::default());
+        assert!(err.is_err());
+
+        let defaults = Defaults {
+            github_host: Some("git.example.org".into()),
+            ..Defaults::default()
+        };
+        let remote = resolve_remote(repo.path(), None, &defaults).unwrap();
+        assert_eq!(remote.kind, ForgeKind::GitHub);
+
+        let defaults = Defaults {
+            forge: Some(ForgeKind::Gitea),
+            ..Defaults::default()
+        };
+        let remote = resolve_remote(repo.path(), None, &defaults).unwrap();
+        assert_eq!(remote.kind, ForgeKind::Gitea);
+        assert_eq!(remote.host, "git.example.org");
+    }
+
+    #[test]
+    fn test_resolve_remote_forge_override_allows_nested_groups() {
+        let repo = TempRepo::new();
+        repo.git(&[
+            "remote",
+            "add",
+            "origin",
+            "git@git.example.org:platform/backend/app.git",
+        ]);
+        assert!(resolve_remote(repo.path(), None, &Defaults::default()).is_err());
+
+        let defaults = Defaults {
+            forge: Some(ForgeKind::GitLab),
+            ..Defaults::default()
+        };
+        let remote = resolve_remote(repo.path(), None, &defaults).unwrap();
+        assert_eq!(remote.kind, ForgeKind::GitLab);
+        assert_eq!(remote.path, "platform/backend/app");
+
+        let remote = resolve_remote(
+            repo.path(),
+            Some("https://git.example.org/platform/web/site.git"),
+            &defaults,
+        )
+        .unwrap();
+        assert_eq!(remote.path, "platform/web/site");
+    }
+
     #[test]
     fn test_normalize_release_title_uses_unreleased_display_tag_for_head() {
         let title = normalize_release_title("HEAD - Draft changes".into(), "Unreleased", "HEAD");
@@ -538,17 +1033,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -580,17 +1078,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -642,17 +1143,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: Some("Extra instructions".into()),
             context: Some("Test project".into()),
-            github_client: Some(gh),
+            forge: Some(ForgeClient::GitHub(gh)),
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -662,72 +1166,276 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_publish_updates_release() {
+    async fn test_generate_offline_notes_feeds_changelog() {
+        let repo = TempRepo::new();
+        repo.write_file("CHANGELOG.md", "# Changelog\n\n## [Unreleased]\n");
+        repo.commit("initial");
+        repo.tag("v0.9.0");
+        repo.write_file("src/main.rs", "fn main() {}");
+        repo.commit("Add feature (#1)");
+        repo.write_file("src/main.rs", "fn main() { }");
+        repo.commit("fix: off-by-one in range");
+        repo.tag("v1.0.0");
+
         let server = MockServer::start().await;
         Mock::given(method("GET"))
-            .and(path("/repos/test/repo/releases/tags/v1.0.0"))
+            .and(path("/repos/test/repo/pulls/1"))
             .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-                "id": 42, "tag_name": "v1.0.0", "name": "v1.0.0", "body": "old"
+                "number": 1, "title": "Add plugin support", "body": null,
+                "user": {"login": "alice"}, "labels": [{"name": "enhancement"}]
             })))
             .mount(&server)
             .await;
-        Mock::given(method("PATCH"))
-            .and(path("/repos/test/repo/releases/42"))
-            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 42})))
-            .expect(1)
-            .mount(&server)
-            .await;
-
         let gh =
             github::GitHubClient::with_base_url("test-token".into(), "test/repo", server.uri())
                 .unwrap();
 
         let ctx = Context {
-            repo_root: PathBuf::from("/tmp"),
-            owner_repo: "test/repo".into(),
-            tag: "v1.0.0".into(),
-            prev_tag: "v0.9.0".into(),
-            client: Box::new(MockLlmClient::new(vec![])),
-            defaults: Defaults::default(),
-            system_extra: None,
-            context: None,
-            github_client: Some(gh),
-        };
-
-        let opts = GenerateOptions {
-            github_release: true,
-            dry_run: false,
-            ..test_opts("v1.0.0")
-        };
-
-        let parsed = ParsedOutput {
-            changelog: "changes".into(),
-            release_title: "Title".into(),
-            release_body: "Body".into(),
-            usage: Usage::default(),
+            client: None,
+            forge: Some(ForgeClient::GitHub(gh)),
+            ..test_context(repo.path().to_path_buf(), "v1.0.0", "v0.9.0")
         };
-
         let job = Arc::new(ProgressJobBuilder::new().build());
-        publish(&opts, &ctx, &parsed, &job).await.unwrap();
-        // wiremock expect(1) verifies PATCH was called
+        let parsed = generate_offline_notes(&ctx, &job).await.unwrap();
+        assert_eq!(parsed.release_title, "1 added, 1 fixed");
+        assert!(
+            parsed
+                .release_body
+                .starts_with("## Added\n\n- Add plugin support (#1) by @alice")
+        );
+
+        update_changelog(&ctx, &parsed, false, &job).await.unwrap();
+        let changelog = std::fs::read_to_string(repo.path().join("CHANGELOG.md")).unwrap();
+        assert!(changelog.contains("## [1.0.0]("));
+        assert!(changelog.contains(
+            "## Added\n\n- Add plugin support ([#1](https://github.com/test/repo/pull/1))"
+        ));
+        assert!(changelog.contains("## Fixed\n\n- Off-by-one in range ("));
+    }
+
+    #[tokio::test]
+    async fn test_generate_offline_notes_lists_merged_pr_once() {
+        let repo = TempRepo::new();
+        repo.write_file("src/main.rs", "fn main() {}");
+        repo.commit("initial");
+        repo.tag("v0.9.0");
+        repo.git(&["checkout", "-b", "topic"]);
+        repo.write_file("src/plugin.rs", "pub fn load() {}");
+        repo.commit("feat: add plugin loader");
+        repo.write_file("src/plugin.rs", "pub fn load() { }");
+        repo.commit("fix: typo in loader");
+        repo.git(&["checkout", "-"]);
+        repo.write_file("README.md", "# app");
+        repo.commit("chore: readme");
+        repo.git(&[
+            "merge",
+            "--no-ff",
+            "topic",
+            "-m",
+            "Merge pull request #5 from alice/topic",
+        ]);
+        repo.tag("v1.0.0");
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test/repo/pulls/5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "number": 5, "title": "Add plugin system", "body": null,
+                "user": {"login": "alice"}, "labels": [{"name": "enhancement"}]
+            })))
+            .mount(&server)
+            .await;
+        let gh =
+            github::GitHubClient::with_base_url("test-token".into(), "test/repo", server.uri())
+                .unwrap();
+
+        let ctx = Context {
+            client: None,
+            forge: Some(ForgeClient::GitHub(gh)),
+            ..test_context(repo.path().to_path_buf(), "v1.0.0", "v0.9.0")
+        };
+        let job = Arc::new(ProgressJobBuilder::new().build());
+        let parsed = generate_offline_notes(&ctx, &job).await.unwrap();
+        assert_eq!(parsed.release_title, "1 added");
+        assert!(!parsed.changelog.contains("loader"), "{}", parsed.changelog);
+
+        // Without the PR, the merge says nothing and its commits stand in.
+        let ctx = Context {
+            client: None,
+            ..test_context(repo.path().to_path_buf(), "v1.0.0", "v0.9.0")
+        };
+        let parsed = generate_offline_notes(&ctx, &job).await.unwrap();
+        assert_eq!(parsed.release_title, "1 added, 1 fixed");
+    }
+
+    #[tokio::test]
+    async fn test_generate_offline_notes_for_package() {
+        let repo = TempRepo::new();
+        repo.write_file("packages/cli/main.rs", "fn main() {}");
+        repo.write_file("packages/sdk/lib.rs", "");
+        repo.commit("initial");
+        repo.tag("cli-v1.0.0");
+        repo.write_file("packages/cli/main.rs", "fn main() { }");
+        repo.commit("feat(cli): add --json");
+        repo.write_file("packages/sdk/lib.rs", "pub fn f() {}");
+        repo.commit("feat(sdk): add f");
+        repo.tag("cli-v1.1.0");
+
+        let ctx = Context {
+            client: None,
+            tag_pattern: Some(TagPattern::new("cli-v*").unwrap()),
+            package: Some(Package {
+                name: "cli".into(),
+                paths: vec!["packages/cli".into()],
+                changelog: Some("packages/cli/CHANGELOG.md".into()),
+            }),
+            ..test_context(repo.path().to_path_buf(), "cli-v1.1.0", "cli-v1.0.0")
+        };
+        let job = Arc::new(ProgressJobBuilder::new().build());
+        let parsed = generate_offline_notes(&ctx, &job).await.unwrap();
+        assert_eq!(parsed.release_title, "1 added");
+        assert!(parsed.changelog.contains("Add --json"));
+        assert!(!parsed.changelog.contains("Add f"));
+
+        update_changelog(&ctx, &parsed, false, &job).await.unwrap();
+        let changelog =
+            std::fs::read_to_string(repo.path().join("packages/cli/CHANGELOG.md")).unwrap();
+        assert!(changelog.contains("## [1.1.0]("));
+        assert!(!repo.path().join("CHANGELOG.md").exists());
+    }
+
+    #[test]
+    fn test_changelog_version_keeps_tag_in_shared_changelog() {
+        let mut ctx = Context {
+            tag_pattern: Some(TagPattern::new("cli-v*").unwrap()),
+            package: Some(Package {
+                name: "cli".into(),
+                paths: vec!["packages/cli".into()],
+                changelog: None,
+            }),
+            ..test_context(PathBuf::from("/tmp"), "cli-v1.4.0", "cli-v1.3.0")
+        };
+        assert_eq!(ctx.changelog_version(), "cli-v1.4.0");
+
+        ctx.package.as_mut().unwrap().changelog = Some("packages/cli/CHANGELOG.md".into());
+        assert_eq!(ctx.changelog_version(), "1.4.0");
+    }
+
+    #[tokio::test]
+    async fn test_publish_updates_release() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test/repo/releases/tags/v1.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 42, "tag_name": "v1.0.0", "name": "v1.0.0", "body": "old"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test/repo/releases/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 42})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let gh =
+            github::GitHubClient::with_base_url("test-token".into(), "test/repo", server.uri())
+                .unwrap();
+
+        let ctx = Context {
+            repo_root: PathBuf::from("/tmp"),
+            remote: Remote::github("test/repo"),
+            tag: "v1.0.0".into(),
+            prev_tag: "v0.9.0".into(),
+            client: Some(Box::new(MockLlmClient::new(vec![]))),
+            defaults: Defaults::default(),
+            system_extra: None,
+            context: None,
+            forge: Some(ForgeClient::GitHub(gh)),
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
+        };
+
+        let opts = GenerateOptions {
+            publish_release: true,
+            dry_run: false,
+            ..test_opts("v1.0.0")
+        };
+
+        let parsed = ParsedOutput {
+            changelog: "changes".into(),
+            release_title: "Title".into(),
+            release_body: "Body".into(),
+            usage: Usage::default(),
+        };
+
+        let job = Arc::new(ProgressJobBuilder::new().build());
+        publish(&opts, &ctx, &parsed, &job).await.unwrap();
+        // wiremock expect(1) verifies PATCH was called
+        assert!(job.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_publish_warns_that_release_flags_need_creation() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test/repo/releases/tags/v1.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 42, "tag_name": "v1.0.0", "name": "v1.0.0", "body": "old"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/test/repo/releases/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 42})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let gh =
+            github::GitHubClient::with_base_url("test-token".into(), "test/repo", server.uri())
+                .unwrap();
+        let ctx = Context {
+            forge: Some(ForgeClient::GitHub(gh)),
+            ..test_context(PathBuf::from("/tmp"), "v1.0.0", "v0.9.0")
+        };
+        let opts = GenerateOptions {
+            create_release: true,
+            draft: true,
+            ..test_opts("v1.0.0")
+        };
+        let parsed = ParsedOutput {
+            changelog: "changes".into(),
+            release_title: "Title".into(),
+            release_body: "Body".into(),
+            usage: Usage::default(),
+        };
+
+        let job = Arc::new(ProgressJobBuilder::new().build());
+        publish(&opts, &ctx, &parsed, &job).await.unwrap();
+        assert!(!job.is_running(), "expected a warning status");
     }
 
     #[tokio::test]
     async fn test_publish_dry_run_skips_update() {
         let ctx = Context {
             repo_root: PathBuf::from("/tmp"),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(MockLlmClient::new(vec![])),
+            client: Some(Box::new(MockLlmClient::new(vec![]))),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let opts = GenerateOptions {
-            github_release: true,
+            publish_release: true,
             dry_run: true,
             ..test_opts("v1.0.0")
         };
@@ -764,18 +1472,21 @@ mod tests {
 
         let ctx = Context {
             repo_root: PathBuf::from("/tmp"),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(MockLlmClient::new(vec![])),
+            client: Some(Box::new(MockLlmClient::new(vec![]))),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: Some(gh),
+            forge: Some(ForgeClient::GitHub(gh)),
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let opts = GenerateOptions {
-            github_release: true,
+            publish_release: true,
             dry_run: false,
             ..test_opts("v1.0.0")
         };
@@ -792,100 +1503,51 @@ mod tests {
         publish(&opts, &ctx, &parsed, &job).await.unwrap();
     }
 
-    #[test]
-    fn test_read_changelog_entry_found() {
-        let dir = tempfile::tempdir().unwrap();
-        std::fs::write(
-            dir.path().join("CHANGELOG.md"),
-            "## [1.0.0]\n### Added\n- Feature\n\n## [0.9.0]\n### Fixed\n- Bug\n",
-        )
-        .unwrap();
-        let entry = read_changelog_entry(dir.path(), "v1.0.0").unwrap();
-        assert!(entry.contains("### Added"));
-        assert!(entry.contains("Feature"));
-        assert!(!entry.contains("0.9.0"));
-    }
-
-    #[test]
-    fn test_read_changelog_entry_not_found() {
-        let dir = tempfile::tempdir().unwrap();
-        std::fs::write(dir.path().join("CHANGELOG.md"), "## [0.9.0]\n- old\n").unwrap();
-        let entry = read_changelog_entry(dir.path(), "v2.0.0");
-        assert!(entry.is_none());
-    }
-
-    #[test]
-    fn test_read_changelog_entry_no_file() {
-        let dir = tempfile::tempdir().unwrap();
-        let entry = read_changelog_entry(dir.path(), "v1.0.0");
-        assert!(entry.is_none());
-    }
-
-    #[test]
-    fn test_read_changelog_entry_alt_format() {
-        let dir = tempfile::tempdir().unwrap();
-        std::fs::write(
-            dir.path().join("CHANGELOG.md"),
-            "## 1.0.0\n### Changed\n- Something\n",
-        )
-        .unwrap();
-        let entry = read_changelog_entry(dir.path(), "v1.0.0").unwrap();
-        assert!(entry.contains("### Changed"));
-    }
-
-    #[test]
-    fn test_read_changelog_entry_allows_level_two_categories() {
-        let dir = tempfile::tempdir().unwrap();
-        std::fs::write(
-            dir.path().join("CHANGELOG.md"),
-            "## [1.0.0]\n## Added\n- Feature\n\n## Fixed\n- Bug\n\n## [0.9.0]\n## Fixed\n- Old\n",
-        )
-        .unwrap();
-
-        let entry = read_changelog_entry(dir.path(), "v1.0.0").unwrap();
-        assert!(entry.contains("## Added"));
-        assert!(entry.contains("## Fixed\n- Bug"));
-        assert!(!entry.contains("0.9.0"));
-    }
-
-    #[test]
-    fn test_read_unreleased_section_bracketed() {
-        let dir = tempfile::tempdir().unwrap();
-        std::fs::write(
-            dir.path().join("CHANGELOG.md"),
-            "# Changelog\n\n## [Unreleased]\n\n### Changed\n- Draft\n\n## [1.0.0]\n- Old\n",
-        )
-        .unwrap();
-
-        let entry = read_unreleased_section(dir.path()).unwrap().unwrap();
-        assert_eq!(entry, "### Changed\n- Draft");
-        assert!(!entry.contains("1.0.0"));
-    }
-
-    #[test]
-    fn test_read_unreleased_section_plain() {
-        let dir = tempfile::tempdir().unwrap();
-        std::fs::write(
-            dir.path().join("CHANGELOG.md"),
-            "# Changelog\n\n## Unreleased\n\n### Fixed\n- Draft bug\n\n## 1.0.0\n- Old\n",
-        )
-        .unwrap();
-
-        let entry = read_unreleased_section(dir.path()).unwrap().unwrap();
-        assert_eq!(entry, "### Fixed\n- Draft bug");
-    }
+    #[tokio::test]
+    async fn test_publish_creates_missing_release() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test/repo/releases/tags/v2.0.0-rc.1"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/test/repo/releases"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        // The -rc.1 suffix makes it a prerelease without --prerelease.
+        Mock::given(method("POST"))
+            .and(path("/repos/test/repo/releases"))
+            .and(body_partial_json(json!({
+                "tag_name": "v2.0.0-rc.1",
+                "name": "Title",
+                "body": "Body",
+                "draft": true,
+                "prerelease": true
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 1})))
+            .expect(1)
+            .mount(&server)
+            .await;
 
-    #[test]
-    fn test_read_unreleased_section_missing_file_or_section() {
-        let dir = tempfile::tempdir().unwrap();
-        assert!(read_unreleased_section(dir.path()).unwrap().is_none());
+        let gh =
+            github::GitHubClient::with_base_url("test-token".into(), "test/repo", server.uri())
+                .unwrap();
+        let ctx = Context {
+            forge: Some(ForgeClient::GitHub(gh)),
+            ..test_context(PathBuf::from("/tmp"), "v2.0.0-rc.1", "v1.9.0")
+        };
+        let opts = GenerateOptions {
+            create_release: true,
+            draft: true,
+            ..test_opts("v2.0.0-rc.1")
+        };
 
-        std::fs::write(
-            dir.path().join("CHANGELOG.md"),
-            "# Changelog\n\n## [1.0.0]\n- Old\n",
-        )
-        .unwrap();
-        assert!(read_unreleased_section(dir.path()).unwrap().is_none());
+        let job = Arc::new(ProgressJobBuilder::new().build());
+        publish(&opts, &ctx, &test_parsed_output("changes"), &job)
+            .await
+            .unwrap();
     }
 
     #[tokio::test]
@@ -925,17 +1587,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -1007,17 +1672,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -1144,17 +1812,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: Some(gh),
+            forge: Some(ForgeClient::GitHub(gh)),
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -1164,7 +1835,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_e2e_publish_github_release() {
+    async fn test_e2e_publish_release() {
         let repo = TempRepo::new();
         repo.write_file("README.md", "# Project");
         repo.commit("initial");
@@ -1217,21 +1888,24 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: Some(gh),
+            forge: Some(ForgeClient::GitHub(gh)),
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let opts = GenerateOptions {
-            github_release: true,
+            publish_release: true,
             dry_run: false,
             ..test_opts("v1.0.0")
         };
@@ -1279,17 +1953,20 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults {
                 verify_links: Some(false),
                 ..Defaults::default()
             },
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let job = Arc::new(ProgressJobBuilder::new().build());
@@ -1298,283 +1975,57 @@ mod tests {
         assert_eq!(parsed.usage.output_tokens, 125);
     }
 
-    #[test]
-    fn test_split_changelog_small() {
-        let content =
-            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2025-01-01\n### Added\n- Feature\n";
-        let (head, tail) = split_changelog(content, 3);
-        assert_eq!(head, content);
-        assert_eq!(tail, "");
-    }
+    #[tokio::test]
+    async fn test_update_changelog_head_updates_unreleased_in_place() {
+        let repo = TempRepo::new();
+        let original = "# Changelog\n\nIntro text.\n\n## [Unreleased]\n\n### Changed\n- Old draft\n\n## [0.1.0] - 2026-01-01\n### Added\n- Initial release\n";
+        repo.write_file("CHANGELOG.md", original);
 
-    #[test]
-    fn test_split_changelog_large() {
-        let content = "\
-# Changelog
+        let ctx = test_context(repo.path().to_path_buf(), "HEAD", "v0.1.0");
+        let parsed = test_parsed_output("### Added\n- New draft");
+        let job = Arc::new(ProgressJobBuilder::new().build());
 
-## [Unreleased]
+        update_changelog(&ctx, &parsed, false, &job).await.unwrap();
 
-## [3.0.0] - 2025-03-01
-### Added
-- Three
+        let result = std::fs::read_to_string(repo.path().join("CHANGELOG.md")).unwrap();
+        assert!(result.contains("Intro text."));
+        assert!(result.contains("## [Unreleased]\n\n### Added\n- New draft"));
+        assert!(result.contains("## [0.1.0] - 2026-01-01\n### Added\n- Initial release"));
+        assert!(!result.contains("## [HEAD]"));
+        assert!(!result.contains("## HEAD"));
+    }
 
-## [2.0.0] - 2025-02-01
-### Added
-- Two
+    #[tokio::test]
+    async fn test_update_changelog_head_creates_missing_file() {
+        let repo = TempRepo::new();
+        let ctx = test_context(repo.path().to_path_buf(), "HEAD", "v0.1.0");
+        let parsed = test_parsed_output("### Added\n- New draft");
+        let job = Arc::new(ProgressJobBuilder::new().build());
 
-## [1.0.0] - 2025-01-01
-### Added
-- One
+        update_changelog(&ctx, &parsed, false, &job).await.unwrap();
 
-## [0.9.0] - 2024-12-01
-### Fixed
-- Zero nine
-";
-        let (head, tail) = split_changelog(content, 3);
-        assert!(head.contains("[3.0.0]"));
-        assert!(head.contains("[2.0.0]"));
-        assert!(head.contains("[1.0.0]"));
-        assert!(!head.contains("[0.9.0]"));
-        assert!(tail.contains("[0.9.0]"));
+        let result = std::fs::read_to_string(repo.path().join("CHANGELOG.md")).unwrap();
+        assert_eq!(
+            result,
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n- New draft\n"
+        );
     }
 
-    #[test]
-    fn test_split_changelog_ignores_level_two_categories() {
-        let content = "\
-# Changelog
-
-## [Unreleased]
+    #[tokio::test]
+    async fn test_update_changelog_head_errors_without_unreleased_header() {
+        let repo = TempRepo::new();
+        repo.write_file("CHANGELOG.md", "# Changelog\n\n## [0.1.0]\n- Old\n");
+        let ctx = test_context(repo.path().to_path_buf(), "HEAD", "v0.1.0");
+        let parsed = test_parsed_output("### Added\n- New draft");
+        let job = Arc::new(ProgressJobBuilder::new().build());
 
-## [3.0.0] - 2025-03-01
-## Added
-- Three
+        let err = update_changelog(&ctx, &parsed, false, &job)
+            .await
+            .unwrap_err()
+            .to_string();
 
-## Fixed
-- Three fix
-
-## [2.0.0] - 2025-02-01
-## Added
-- Two
-
-## [1.0.0] - 2025-01-01
-## Added
-- One
-
-## [0.9.0] - 2024-12-01
-## Fixed
-- Zero nine
-";
-
-        let (head, tail) = split_changelog(content, 3);
-        assert!(head.contains("## Added\n- Three"));
-        assert!(head.contains("## Fixed\n- Three fix"));
-        assert!(head.contains("[1.0.0]"));
-        assert!(!head.contains("[0.9.0]"));
-        assert!(tail.starts_with("## [0.9.0]"));
-    }
-
-    #[test]
-    fn test_split_changelog_ignores_digit_leading_non_versions() {
-        let content = "\
-# Changelog
-
-## [Unreleased]
-
-## [3.0.0]
-- Three
-
-## 2024 Retrospective
-- Not a version
-
-## 1st Quarter Updates
-- Not a version either
-
-## [2.0.0]
-- Two
-
-## [1.0.0]
-- One
-
-## [0.9.0]
-- Zero nine
-";
-
-        let (head, tail) = split_changelog(content, 3);
-        assert!(head.contains("2024 Retrospective"));
-        assert!(head.contains("1st Quarter Updates"));
-        assert!(!head.contains("[0.9.0]"));
-        assert!(tail.starts_with("## [0.9.0]"));
-    }
-
-    #[test]
-    fn test_repair_changelog_section_boundaries() {
-        let content = "- Fix one ([#1](https://github.com/jdx/communique/pull/1))## [1.0.0]\n## Fixed\n- Old ([#0](https://github.com/jdx/communique/pull/0))## [0.9.0]\n";
-
-        let repaired = repair_changelog_section_boundaries(content);
-
-        assert!(repaired.contains("pull/1))\n\n## [1.0.0]"));
-        assert!(repaired.contains("pull/0))\n\n## [0.9.0]"));
-    }
-
-    #[test]
-    fn test_repair_changelog_section_boundaries_ignores_non_versions() {
-        let content = "- Fixed `pattern()## anchor` without changing inline code\n- Also fixed link text)## not a version\n";
-
-        let repaired = repair_changelog_section_boundaries(content);
-
-        assert_eq!(repaired, content);
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_bracketed_preserves_tail() {
-        let existing = "# Changelog\n\n## [Unreleased]\n\n### Changed\n- Old draft\n\n## [1.0.0] - 2025-01-01\n### Added\n- Old release\n";
-        let updated = replace_unreleased_section(existing, "### Added\n- New draft").unwrap();
-
-        assert_eq!(
-            updated,
-            "# Changelog\n\n## [Unreleased]\n\n### Added\n- New draft\n\n## [1.0.0] - 2025-01-01\n### Added\n- Old release\n"
-        );
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_plain_preserves_header() {
-        let existing = "# Changelog\n\n## Unreleased\n\n- Old draft\n";
-        let updated = replace_unreleased_section(existing, "### Fixed\n- New fix").unwrap();
-
-        assert_eq!(
-            updated,
-            "# Changelog\n\n## Unreleased\n\n### Fixed\n- New fix\n"
-        );
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_allows_level_two_categories() {
-        let existing = "# Changelog\n\n## [Unreleased]\n\n## Added\n- thing 1\n\n## Fixed\n- thing 2\n\n## [1.0.0] - 2026-01-01\n- old release\n";
-        let updated = replace_unreleased_section(existing, "## Added\n- replacement").unwrap();
-
-        assert_eq!(
-            updated,
-            "# Changelog\n\n## [Unreleased]\n\n## Added\n- replacement\n\n## [1.0.0] - 2026-01-01\n- old release\n"
-        );
-        assert!(!updated.contains("thing 1"));
-        assert!(!updated.contains("thing 2"));
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_stops_at_unbracketed_v_version() {
-        let existing =
-            "# Changelog\n\n## [Unreleased]\n\n## Added\n- old draft\n\n## v1.0.0\n- old release\n";
-        let updated = replace_unreleased_section(existing, "## Fixed\n- replacement").unwrap();
-
-        assert_eq!(
-            updated,
-            "# Changelog\n\n## [Unreleased]\n\n## Fixed\n- replacement\n\n## v1.0.0\n- old release\n"
-        );
-        assert!(!updated.contains("old draft"));
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_preserves_linked_header() {
-        let existing = "# Changelog\n\n## [Unreleased](https://example.com/compare/v1.0.0...HEAD)\n\n- Old draft\n";
-        let updated = replace_unreleased_section(existing, "- New draft").unwrap();
-
-        assert_eq!(
-            updated,
-            "# Changelog\n\n## [Unreleased](https://example.com/compare/v1.0.0...HEAD)\n\n- New draft\n"
-        );
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_errors_without_unreleased_header() {
-        let err = replace_unreleased_section("# Changelog\n\n## [1.0.0]\n- Old\n", "- New")
-            .unwrap_err()
-            .to_string();
-
-        assert!(err.contains("does not contain ## [Unreleased] or ## Unreleased"));
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_errors_on_duplicate_unreleased_headers() {
-        let err = replace_unreleased_section(
-            "# Changelog\n\n## [Unreleased]\n\n## Unreleased\n",
-            "- New",
-        )
-        .unwrap_err()
-        .to_string();
-
-        assert!(err.contains("multiple Unreleased sections"));
-    }
-
-    #[test]
-    fn test_replace_unreleased_section_removes_forbidden_target_headers() {
-        let existing = "# Changelog\n\n## [Unreleased]\n\n- Old\n";
-        let updated = replace_unreleased_section(existing, "## [HEAD]\n### Added\n- New").unwrap();
-
-        assert!(!updated.contains("## [HEAD]"));
-        assert!(!updated.contains("## HEAD"));
-        assert!(updated.contains("### Added\n- New"));
-    }
-
-    #[test]
-    fn test_normalize_generated_changelog_body_removes_linked_head_header() {
-        let body = normalize_generated_changelog_body(
-            "## [HEAD](https://example.com/compare/v1.0.0...HEAD)\n## Added\n- New",
-        );
-
-        assert_eq!(body, "## Added\n- New");
-    }
-
-    #[tokio::test]
-    async fn test_update_changelog_head_updates_unreleased_in_place() {
-        let repo = TempRepo::new();
-        let original = "# Changelog\n\nIntro text.\n\n## [Unreleased]\n\n### Changed\n- Old draft\n\n## [0.1.0] - 2026-01-01\n### Added\n- Initial release\n";
-        repo.write_file("CHANGELOG.md", original);
-
-        let ctx = test_context(repo.path().to_path_buf(), "HEAD", "v0.1.0");
-        let parsed = test_parsed_output("### Added\n- New draft");
-        let job = Arc::new(ProgressJobBuilder::new().build());
-
-        update_changelog(&ctx, &parsed, false, &job).await.unwrap();
-
-        let result = std::fs::read_to_string(repo.path().join("CHANGELOG.md")).unwrap();
-        assert!(result.contains("Intro text."));
-        assert!(result.contains("## [Unreleased]\n\n### Added\n- New draft"));
-        assert!(result.contains("## [0.1.0] - 2026-01-01\n### Added\n- Initial release"));
-        assert!(!result.contains("## [HEAD]"));
-        assert!(!result.contains("## HEAD"));
-    }
-
-    #[tokio::test]
-    async fn test_update_changelog_head_creates_missing_file() {
-        let repo = TempRepo::new();
-        let ctx = test_context(repo.path().to_path_buf(), "HEAD", "v0.1.0");
-        let parsed = test_parsed_output("### Added\n- New draft");
-        let job = Arc::new(ProgressJobBuilder::new().build());
-
-        update_changelog(&ctx, &parsed, false, &job).await.unwrap();
-
-        let result = std::fs::read_to_string(repo.path().join("CHANGELOG.md")).unwrap();
-        assert_eq!(
-            result,
-            "# Changelog\n\n## [Unreleased]\n\n### Added\n- New draft\n"
-        );
-    }
-
-    #[tokio::test]
-    async fn test_update_changelog_head_errors_without_unreleased_header() {
-        let repo = TempRepo::new();
-        repo.write_file("CHANGELOG.md", "# Changelog\n\n## [0.1.0]\n- Old\n");
-        let ctx = test_context(repo.path().to_path_buf(), "HEAD", "v0.1.0");
-        let parsed = test_parsed_output("### Added\n- New draft");
-        let job = Arc::new(ProgressJobBuilder::new().build());
-
-        let err = update_changelog(&ctx, &parsed, false, &job)
-            .await
-            .unwrap_err()
-            .to_string();
-
-        assert!(err.contains("does not contain ## [Unreleased] or ## Unreleased"));
-    }
+        assert!(err.contains("does not contain ## [Unreleased] or ## Unreleased"));
+    }
 
     #[tokio::test]
     async fn test_update_changelog_head_dry_run_leaves_file_unchanged() {
@@ -1627,14 +2078,17 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let parsed = ParsedOutput {
@@ -1683,14 +2137,17 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let parsed = ParsedOutput {
@@ -1728,14 +2185,17 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.0.0".into(),
             prev_tag: "v0.9.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let parsed = ParsedOutput {
@@ -1816,14 +2276,17 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v4.0.0".into(),
             prev_tag: "v3.0.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let parsed = ParsedOutput {
@@ -1886,14 +2349,17 @@ mod tests {
 
         let ctx = Context {
             repo_root: repo.path().to_path_buf(),
-            owner_repo: "test/repo".into(),
+            remote: Remote::github("test/repo"),
             tag: "v1.1.0".into(),
             prev_tag: "v1.0.0".into(),
-            client: Box::new(mock_client),
+            client: Some(Box::new(mock_client)),
             defaults: Defaults::default(),
             system_extra: None,
             context: None,
-            github_client: None,
+            forge: None,
+            tools_config: config::ToolsConfig::default(),
+            tag_pattern: None,
+            package: None,
         };
 
         let parsed = ParsedOutput {
@@ -1990,37 +2456,6 @@ mod tests {
         assert!(err.contains("multiple Unreleased sections"));
     }
 
-    #[test]
-    fn test_format_version_header_matches_existing_styles() {
-        assert_eq!(
-            format_version_header(
-                "# Changelog\n\n## [1.0.0](https://example.com) - 2025-01-01\n",
-                "v1.1.0",
-                "2025-02-01",
-                "https://github.com/test/repo/releases/tag/v1.1.0",
-            ),
-            "## [1.1.0](https://github.com/test/repo/releases/tag/v1.1.0) - 2025-02-01"
-        );
-        assert_eq!(
-            format_version_header(
-                "# Changelog\n\n## v1.0.0 - 2025-01-01\n",
-                "1.1.0",
-                "2025-02-01",
-                "https://github.com/test/repo/releases/tag/v1.1.0",
-            ),
-            "## v1.1.0 - 2025-02-01"
-        );
-        assert_eq!(
-            format_version_header(
-                "# Changelog\n\n## [1.0.0]\n",
-                "1.1.0",
-                "2025-02-01",
-                "https://github.com/test/repo/releases/tag/v1.1.0",
-            ),
-            "## [1.1.0]"
-        );
-    }
-
     #[test]
     fn test_today_iso() {
         let date = today_iso();
@@ -2030,412 +2465,3 @@ mod tests {
         assert_eq!(date.as_bytes()[7], b'-');
     }
 }
-
-/// Split changelog content into (head, tail) keeping at most `max_versions` versioned sections
-/// in the head. This limits tokens sent to the LLM for large changelogs.
-#[cfg(test)]
-fn split_changelog(content: &str, max_versions: usize) -> (&str, &str) {
-    let mut version_count = 0;
-
-    for (start, line) in content.lines_with_offset() {
-        if !is_version_header(line) {
-            continue;
-        }
-
-        version_count += 1;
-        if version_count > max_versions {
-            return (&content[..start], &content[start..]);
-        }
-    }
-
-    (content, "")
-}
-
-/// Returns today's date as YYYY-MM-DD using std only.
-fn today_iso() -> String {
-    // Use UNIX_EPOCH + SystemTime to get days, then Hinnant civil_from_days
-    let secs = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let days = (secs / 86400) as i64;
-
-    // Hinnant's civil_from_days (epoch = 1970-01-01 = day 0)
-    let z = days + 719468;
-    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
-    let doe = (z - era * 146097) as u64;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = (yoe as i64) + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if m <= 2 { y + 1 } else { y };
-
-    format!("{y:04}-{m:02}-{d:02}")
-}
-
-#[derive(Debug, Clone, Copy)]
-struct ChangelogSection {
-    body_start: usize,
-    body_end: usize,
-}
-
-fn is_unreleased_header(line: &str) -> bool {
-    let line = line.trim();
-    line == "## Unreleased" || line == "## [Unreleased]" || line.starts_with("## [Unreleased](")
-}
-
-fn is_forbidden_unreleased_target_header(line: &str) -> bool {
-    let line = line.trim();
-    line == "## Unreleased"
-        || line == "## [Unreleased]"
-        || line.starts_with("## [Unreleased](")
-        || line == "## HEAD"
-        || line == "## [HEAD]"
-        || line.starts_with("## [HEAD](")
-}
-
-fn is_version_header(line: &str) -> bool {
-    header_version(line).is_some()
-}
-
-fn header_version(line: &str) -> Option<&str> {
-    let rest = line.trim().strip_prefix("## ")?;
-    let rest = rest.trim_start();
-
-    if let Some(bracketed) = rest.strip_prefix('[') {
-        let (version, _) = bracketed.split_once(']')?;
-        return is_version_like(version).then_some(version);
-    }
-
-    let version = rest
-        .split_once(char::is_whitespace)
-        .map_or(rest, |(version, _)| version);
-    is_version_like(version).then_some(version)
-}
-
-fn is_version_like(value: &str) -> bool {
-    let value = value.strip_prefix('v').unwrap_or(value);
-    value.chars().next().is_some_and(|c| c.is_ascii_digit()) && value.contains('.')
-}
-
-trait LinesWithOffset {
-    fn lines_with_offset(&self) -> LinesWithOffsetIter<'_>;
-}
-
-impl LinesWithOffset for str {
-    fn lines_with_offset(&self) -> LinesWithOffsetIter<'_> {
-        LinesWithOffsetIter {
-            content: self,
-            offset: 0,
-        }
-    }
-}
-
-struct LinesWithOffsetIter<'a> {
-    content: &'a str,
-    offset: usize,
-}
-
-impl<'a> Iterator for LinesWithOffsetIter<'a> {
-    type Item = (usize, &'a str);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.offset >= self.content.len() {
-            return None;
-        }
-
-        let start = self.offset;
-        let rest = &self.content[start..];
-        let line_len = rest.find('\n').map_or(rest.len(), |idx| idx + 1);
-        self.offset += line_len;
-        Some((start, &self.content[start..start + line_len]))
-    }
-}
-
-fn find_version_section_start(contents: &str, version: &str) -> Option<usize> {
-    let version = version.strip_prefix('v').unwrap_or(version);
-
-    contents
-        .lines_with_offset()
-        .find(|(_, line)| {
-            header_version(line)
-                .map(|header| header.strip_prefix('v').unwrap_or(header) == version)
-                .unwrap_or(false)
-        })
-        .map(|(start, _)| start)
-}
-
-fn find_next_version_section_start(contents: &str, after: usize) -> Option<usize> {
-    contents
-        .lines_with_offset()
-        .find(|(start, line)| *start > after && is_version_header(line))
-        .map(|(start, _)| start)
-}
-
-fn join_changelog_head_tail(head: &str, tail: &str) -> String {
-    let head = head.trim_end();
-    let tail = tail.trim_start_matches(['\r', '\n']);
-
-    if tail.is_empty() {
-        head.to_string()
-    } else if head.is_empty() {
-        tail.to_string()
-    } else {
-        format!("{head}\n\n{tail}")
-    }
-}
-
-fn format_version_header(existing: &str, version: &str, date: &str, release_url: &str) -> String {
-    let version = version.strip_prefix('v').unwrap_or(version);
-    let template = existing
-        .lines_with_offset()
-        .find(|(_, line)| is_version_header(line))
-        .map(|(_, line)| line.trim());
-
-    let Some(template) = template else {
-        return format!("## [{version}]({release_url}) - {date}");
-    };
-
-    if template.starts_with("## [") {
-        if template.contains("](") {
-            if template.contains(" - ") {
-                format!("## [{version}]({release_url}) - {date}")
-            } else {
-                format!("## [{version}]({release_url})")
-            }
-        } else if template.contains(" - ") {
-            format!("## [{version}] - {date}")
-        } else {
-            format!("## [{version}]")
-        }
-    } else {
-        let prefix = if template.trim_start_matches("## ").starts_with('v') {
-            "v"
-        } else {
-            ""
-        };
-        if template.contains(" - ") {
-            format!("## {prefix}{version} - {date}")
-        } else {
-            format!("## {prefix}{version}")
-        }
-    }
-}
-
-fn remove_version_section(contents: &str, version: &str) -> String {
-    let Some(start) = find_version_section_start(contents, version) else {
-        return contents.to_string();
-    };
-    let end = find_next_version_section_start(contents, start).unwrap_or(contents.len());
-    join_changelog_head_tail(&contents[..start], &contents[end..])
-}
-
-fn upsert_version_changelog(
-    existing: &str,
-    tag: &str,
-    date: &str,
-    release_url: &str,
-    changelog: &str,
-) -> miette::Result<String> {
-    let existing = repair_changelog_section_boundaries(existing);
-    let version = tag.strip_prefix('v').unwrap_or(tag);
-    let header = format_version_header(&existing, version, date, release_url);
-    let body = changelog.trim();
-    let section = if body.is_empty() {
-        header
-    } else {
-        format!("{header}\n\n{body}")
-    };
-
-    let without_existing = remove_version_section(&existing, version);
-    let Some(unreleased) = find_unreleased_section(&without_existing)? else {
-        return Err(miette::miette!(
-            "CHANGELOG.md exists but does not contain ## [Unreleased] or ## Unreleased; add an Unreleased section before running `communique generate {} --changelog`.",
-            tag
-        ));
-    };
-
-    let before = without_existing[..unreleased.body_end].trim_end();
-    let after = without_existing[unreleased.body_end..].trim_start_matches(['\r', '\n']);
-    let updated = if after.is_empty() {
-        format!("{before}\n\n{section}")
-    } else {
-        format!("{before}\n\n{section}\n\n{after}")
-    };
-
-    Ok(format!("{}\n", updated.trim_end()))
-}
-
-fn repair_changelog_section_boundaries(content: &str) -> String {
-    let mut repaired = String::with_capacity(content.len());
-    let mut rest = content;
-
-    while let Some(pos) = rest.find(")## ") {
-        let (before, after_marker) = rest.split_at(pos + 1);
-        repaired.push_str(before);
-        if is_version_header(after_marker.lines().next().unwrap_or("")) {
-            repaired.push_str("\n\n");
-        }
-        rest = after_marker;
-    }
-
-    repaired.push_str(rest);
-    repaired
-}
-
-fn read_changelog_entry(repo_root: &Path, tag: &str) -> Option<String> {
-    let path = repo_root.join("CHANGELOG.md");
-    let contents = xx::file::read_to_string(&path).ok()?;
-
-    let start = find_version_section_start(&contents, tag)?;
-    let end = find_next_version_section_start(&contents, start).unwrap_or(contents.len());
-
-    Some(contents[start..end].trim().to_string())
-}
-
-fn find_unreleased_section(contents: &str) -> miette::Result<Option<ChangelogSection>> {
-    let mut lines = Vec::new();
-    let mut offset = 0;
-    for line in contents.split_inclusive('\n') {
-        let start = offset;
-        offset += line.len();
-        lines.push((start, offset, line));
-    }
-
-    let mut unreleased_index = None;
-    for (index, (_, _, line)) in lines.iter().enumerate() {
-        if is_unreleased_header(line) && unreleased_index.replace(index).is_some() {
-            return Err(miette::miette!(
-                "CHANGELOG.md contains multiple Unreleased sections; cannot update safely."
-            ));
-        }
-    }
-
-    let Some(index) = unreleased_index else {
-        return Ok(None);
-    };
-
-    let body_start = lines[index].1;
-    let mut body_end = contents.len();
-    for (start, _, line) in lines.iter().skip(index + 1) {
-        if is_version_header(line) {
-            body_end = *start;
-            break;
-        }
-    }
-
-    Ok(Some(ChangelogSection {
-        body_start,
-        body_end,
-    }))
-}
-
-fn normalize_generated_changelog_body(generated: &str) -> String {
-    generated
-        .lines()
-        .filter(|line| !is_forbidden_unreleased_target_header(line))
-        .collect::<Vec<_>>()
-        .join("\n")
-        .trim()
-        .to_string()
-}
-
-fn new_unreleased_changelog(generated: &str) -> String {
-    let body = normalize_generated_changelog_body(generated);
-    if body.is_empty() {
-        "# Changelog\n\n## [Unreleased]\n".to_string()
-    } else {
-        format!("# Changelog\n\n## [Unreleased]\n\n{body}\n")
-    }
-}
-
-fn replace_unreleased_section(existing: &str, generated: &str) -> miette::Result<String> {
-    let Some(section) = find_unreleased_section(existing)? else {
-        return Err(miette::miette!(
-            "CHANGELOG.md exists but does not contain ## [Unreleased] or ## Unreleased; add an Unreleased section before running `communique generate HEAD --changelog`."
-        ));
-    };
-
-    let body = normalize_generated_changelog_body(generated);
-    let mut updated = existing[..section.body_start].trim_end().to_string();
-
-    if !body.is_empty() {
-        updated.push_str("\n\n");
-        updated.push_str(&body);
-    }
-
-    let tail = existing[section.body_end..].trim_start_matches(['\r', '\n']);
-    if !tail.is_empty() {
-        updated.push_str("\n\n");
-        updated.push_str(tail);
-    }
-
-    Ok(format!("{}\n", updated.trim_end()))
-}
-
-fn read_unreleased_section(repo_root: &Path) -> miette::Result<Option<String>> {
-    let path = repo_root.join("CHANGELOG.md");
-    let contents = match std::fs::read_to_string(&path) {
-        Ok(contents) => contents,
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-        Err(err) => return Err(crate::error::Error::Io(err).into()),
-    };
-
-    let Some(section) = find_unreleased_section(&contents)? else {
-        return Ok(None);
-    };
-
-    Ok(Some(
-        contents[section.body_start..section.body_end]
-            .trim()
-            .to_string(),
-    ))
-}
-
-async fn update_changelog(
-    ctx: &Context,
-    parsed: &ParsedOutput,
-    dry_run: bool,
-    job: &Arc<ProgressJob>,
-) -> miette::Result<()> {
-    job.prop("message", "Updating CHANGELOG.md...");
-
-    let changelog_path = ctx.repo_root.join("CHANGELOG.md");
-    if ctx.is_unreleased_head() {
-        debug_assert_eq!(ctx.tag, "HEAD");
-        let updated = match std::fs::read_to_string(&changelog_path) {
-            Ok(existing) => replace_unreleased_section(&existing, &parsed.changelog)?,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                new_unreleased_changelog(&parsed.changelog)
-            }
-            Err(err) => return Err(crate::error::Error::Io(err).into()),
-        };
-
-        if !dry_run {
-            xx::file::write(&changelog_path, updated)?;
-            info!("wrote {}", changelog_path.display());
-        }
-
-        return Ok(());
-    }
-
-    let existing = xx::file::read_to_string(&changelog_path)
-        .unwrap_or_else(|_| "# Changelog\n\n## [Unreleased]\n".to_string());
-
-    let date = today_iso();
-    let release_url = format!(
-        "https://github.com/{}/releases/tag/{}",
-        ctx.owner_repo, ctx.tag
-    );
-
-    let content =
-        upsert_version_changelog(&existing, &ctx.tag, &date, &release_url, &parsed.changelog)?;
-    if !dry_run {
-        xx::file::write(&changelog_path, content)?;
-        info!("wrote {}", changelog_path.display());
-    }
-
-    Ok(())
-}
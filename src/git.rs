@@ -1,74 +1,150 @@
 use std::path::{Path, PathBuf};
 
+use log::warn;
 use regex::Regex;
 use xx::git::Git;
 use xx::process;
 
 use crate::error::{Error, Result};
+use crate::forge::{ForgeKind, Remote};
+use crate::tags::{self, Since, TagPattern, Version};
 
 pub fn repo_root() -> Result<PathBuf> {
     let path = process::cmd("git", ["rev-parse", "--show-toplevel"]).read()?;
     Ok(PathBuf::from(path))
 }
 
-pub fn detect_remote(repo_root: &Path) -> Result<String> {
+/// The forge and project the `origin` remote points at.
+pub fn detect_remote(
+    repo_root: &Path,
+    kind: Option<ForgeKind>,
+    github_host: Option<&str>,
+) -> Result<Remote> {
     let git = Git::new(repo_root.to_path_buf());
     let url = git
         .get_remote_url()
         .ok_or_else(|| Error::Git("no origin remote found".into()))?;
-    parse_owner_repo(&url)
+    Remote::parse(&url, kind, github_host)
 }
 
-fn parse_owner_repo(url: &str) -> Result<String> {
-    // SSH: git@github.com:owner/repo.git
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let repo = rest.trim_end_matches(".git");
-        return Ok(repo.to_string());
+/// The release before `current_tag`: the newest tag, by semver, that is an
+/// ancestor of it, so patches cut from a maintenance branch are compared
+/// against that branch's history. With a `pattern`, only tags of the same
+/// package are considered, and `since` decides whether prereleases count.
+/// `warn_unmerged` warns about newer tags skipped for not being ancestors,
+/// which maintenance branches produce on every release from `main`.
+pub fn previous_tag(
+    repo_root: &Path,
+    current_tag: &str,
+    pattern: Option<&TagPattern>,
+    since: Since,
+    warn_unmerged: bool,
+) -> Result<String> {
+    let target = resolve_ref(repo_root, current_tag)?;
+    let reachable = tags_sorted(repo_root, Some(&target), pattern)?;
+
+    // Newest first by semver; the sort is stable, so tags naming the same
+    // version keep git's order.
+    let mut versioned: Vec<(Version, &str)> = reachable
+        .iter()
+        .filter_map(|t| Some((tags::tag_version(t, pattern)?, t.as_str())))
+        .collect();
+    versioned.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let current = tags::tag_version(current_tag, pattern);
+    let stable_only = since.stable_only(current.as_ref());
+    let mut candidates = versioned
+        .iter()
+        .filter(|(v, _)| !stable_only || !v.is_prerelease());
+
+    if let Some(current) = &current {
+        let Some((prev, prev_tag)) = candidates.find(|(v, _)| v < current) else {
+            warn_no_reachable_tag(repo_root, current_tag, pattern)?;
+            return root_commit(repo_root);
+        };
+        // Tags between the two that aren't ancestors usually mean the range
+        // crosses a branch point and repeats changes already released.
+        let unmerged = if warn_unmerged {
+            tags_sorted(repo_root, None, pattern)?
+        } else {
+            Vec::new()
+        };
+        for tag in unmerged {
+            if reachable.contains(&tag) {
+                continue;
+            }
+            if let Some(v) = tags::tag_version(&tag, pattern)
+                && v > *prev
+                && v < *current
+                && (!stable_only || !v.is_prerelease())
+            {
+                warn!(
+                    "{tag} is newer than {prev_tag} but is not an ancestor of {current_tag}; \
+                     the notes may repeat changes already released in {tag} \
+                     (pass PREV_TAG to override, or --no-ancestry-warning to silence this)"
+                );
+            }
+        }
+        return Ok(prev_tag.to_string());
     }
-    // HTTPS: https://github.com/owner/repo.git
-    if let Some(rest) = url
-        .strip_prefix("https://github.com/")
-        .or_else(|| url.strip_prefix("http://github.com/"))
+
+    // A tag that isn't a version: take the one git sorts after it.
+    if let Some(pos) = reachable.iter().position(|t| t == current_tag) {
+        return match reachable.get(pos + 1) {
+            Some(tag) => Ok(tag.clone()),
+            None => root_commit(repo_root),
+        };
+    }
+
+    // HEAD, a branch or a commit SHA: compare against the newest release,
+    // falling back to the root commit so we capture all history.
+    match candidates
+        .next()
+        .map(|(_, t)| t.to_string())
+        .or(reachable.first().cloned())
     {
-        let repo = rest.trim_end_matches(".git");
-        return Ok(repo.to_string());
+        Some(tag) => Ok(tag),
+        None => {
+            warn_no_reachable_tag(repo_root, current_tag, pattern)?;
+            root_commit(repo_root)
+        }
     }
-    Err(Error::Git(format!(
-        "cannot parse GitHub repo from remote URL: {url}"
-    )))
 }
 
-pub fn previous_tag(repo_root: &Path, current_tag: &str) -> Result<String> {
-    let stdout = process::cmd("git", ["tag", "--sort=-v:refname"])
-        .cwd(repo_root)
-        .read()?;
-
-    let tags: Vec<&str> = stdout
+/// Tags matching `pattern` in git's version order, newest first, limited to
+/// ancestors of `merged` when given.
+fn tags_sorted(
+    repo_root: &Path,
+    merged: Option<&str>,
+    pattern: Option<&TagPattern>,
+) -> Result<Vec<String>> {
+    let mut args = vec!["tag", "--sort=-v:refname"];
+    if let Some(commit) = merged {
+        args.extend(["--merged", commit]);
+    }
+    let stdout = process::cmd("git", &args).cwd(repo_root).read()?;
+    Ok(stdout
         .lines()
         .map(str::trim)
         .filter(|t| !t.is_empty())
-        .collect();
-
-    // If current_tag is in the tag list, return the one after it (next oldest)
-    let mut found = false;
-    for tag in &tags {
-        if found {
-            return Ok(tag.to_string());
-        }
-        if *tag == current_tag {
-            found = true;
-        }
-    }
+        .filter(|t| pattern.is_none_or(|p| p.matches(t)))
+        .map(String::from)
+        .collect())
+}
 
-    // If current_tag was found but there's no previous tag, or if there are
-    // no tags at all, fall back to the root commit so we capture all history.
-    if found || tags.is_empty() {
-        return root_commit(repo_root);
+/// Falling back to the whole history is expected for a first release, but
+/// not when other releases exist on unrelated branches.
+fn warn_no_reachable_tag(
+    repo_root: &Path,
+    current_tag: &str,
+    pattern: Option<&TagPattern>,
+) -> Result<()> {
+    if !tags_sorted(repo_root, None, pattern)?.is_empty() {
+        warn!(
+            "no earlier release tag is an ancestor of {current_tag}; using the full history since the root commit"
+        );
     }
-
-    // current_tag is not in the tag list (e.g. HEAD, branch, commit SHA, or
-    // a version that isn't tagged yet). Fall back to the most recent tag.
-    Ok(tags[0].to_string())
+    Ok(())
 }
 
 fn root_commit(repo_root: &Path) -> Result<String> {
@@ -99,21 +175,350 @@ pub fn resolve_ref(repo_root: &Path, git_ref: &str) -> Result<String> {
     }
 }
 
-pub fn log_between(repo_root: &Path, from: &str, to: &str) -> Result<String> {
+/// Whether `git_ref` names an existing commit, without falling back to HEAD.
+pub fn ref_exists(repo_root: &Path, git_ref: &str) -> bool {
+    process::cmd(
+        "git",
+        [
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{git_ref}^{{commit}}"),
+        ],
+    )
+    .cwd(repo_root)
+    .stderr_capture()
+    .stdout_capture()
+    .run()
+    .is_ok()
+}
+
+/// One-line log of `from..to`, limited to commits touching `paths` when any
+/// are given.
+pub fn log_between(repo_root: &Path, from: &str, to: &str, paths: &[String]) -> Result<String> {
+    let from = resolve_ref(repo_root, from)?;
+    let to = resolve_ref(repo_root, to)?;
+    let range = format!("{from}..{to}");
+    let mut args = vec![
+        "log".to_string(),
+        range,
+        "--pretty=format:%h %s".into(),
+        "--reverse".into(),
+    ];
+    if !paths.is_empty() {
+        args.push("--".into());
+        args.extend(paths.iter().cloned());
+    }
+    let output = process::cmd("git", &args).cwd(repo_root).read()?;
+    Ok(output)
+}
+
+/// A commit in a release range.
+#[derive(Debug, Clone)]
+pub struct Commit {
+    /// Abbreviated SHA.
+    pub sha: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Commits in `from..to`, oldest first, with their full messages. Like
+/// [`log_between`], `paths` limits them to commits touching those paths.
+pub fn commits_between(
+    repo_root: &Path,
+    from: &str,
+    to: &str,
+    paths: &[String],
+) -> Result<Vec<Commit>> {
     let from = resolve_ref(repo_root, from)?;
     let to = resolve_ref(repo_root, to)?;
     let range = format!("{from}..{to}");
-    let output = process::cmd("git", ["log", &range, "--pretty=format:%h %s", "--reverse"])
+    let mut args = vec![
+        "log".to_string(),
+        range,
+        "--pretty=format:%h%x1f%s%x1f%b%x1e".into(),
+        "--reverse".into(),
+    ];
+    if !paths.is_empty() {
+        args.push("--".into());
+        args.extend(paths.iter().cloned());
+    }
+    let output = process::cmd("git", &args).cwd(repo_root).read()?;
+    Ok(output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\x1f');
+            Some(Commit {
+                sha: fields.next().filter(|s| !s.is_empty())?.to_string(),
+                subject: fields.next()?.to_string(),
+                body: fields.next().unwrap_or("").trim().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Abbreviated SHAs of the commits a merge brings in from its second parent,
+/// in the same form as [`Commit::sha`]. Empty for a commit that isn't a merge.
+pub fn merged_commits(repo_root: &Path, merge: &str) -> Result<Vec<String>> {
+    if !ref_exists(repo_root, &format!("{merge}^2")) {
+        return Ok(vec![]);
+    }
+    let output = process::cmd(
+        "git",
+        [
+            "log",
+            "--pretty=format:%h",
+            &format!("{merge}^1..{merge}^2"),
+        ],
+    )
+    .cwd(repo_root)
+    .read()?;
+    Ok(output.lines().map(String::from).collect())
+}
+
+/// A commit undone by a later commit in the same range, so together they
+/// ship nothing.
+#[derive(Debug, Clone)]
+pub struct Revert {
+    pub original: Commit,
+    pub revert: Commit,
+}
+
+/// Split `commits` into those that still matter and the pairs that cancel out.
+/// A revert is matched to its original by the `This reverts commit <sha>`
+/// line git writes, or else by a `Revert "<subject>"` subject. Pairing runs
+/// newest first, so reverting a revert leaves the original in place.
+pub fn pair_reverts(commits: Vec<Commit>) -> (Vec<Commit>, Vec<Revert>) {
+    let reverts_sha_re = Regex::new(r"This reverts commit ([0-9a-f]{7,40})").unwrap();
+    let reverts_subject_re = Regex::new(r#"^Revert "(.+)"(?:\s*\(#\d+\))?$"#).unwrap();
+
+    let mut slots: Vec<Option<Commit>> = commits.into_iter().map(Some).collect();
+    let mut pairs = Vec::new();
+    for i in (0..slots.len()).rev() {
+        let Some(commit) = &slots[i] else { continue };
+        let target_sha = reverts_sha_re
+            .captures(&commit.body)
+            .map(|c| c[1].to_string());
+        let target_subject = reverts_subject_re
+            .captures(&commit.subject)
+            .map(|c| c[1].to_string());
+        if target_sha.is_none() && target_subject.is_none() {
+            continue;
+        }
+        let target = (0..i).rev().find(|&j| {
+            slots[j].as_ref().is_some_and(|c| match &target_sha {
+                Some(sha) => sha.starts_with(&c.sha),
+                None => target_subject.as_deref() == Some(c.subject.as_str()),
+            })
+        });
+        if let Some(j) = target {
+            let revert = slots[i].take().unwrap();
+            let original = slots[j].take().unwrap();
+            pairs.push(Revert { original, revert });
+        }
+    }
+    pairs.reverse();
+    (slots.into_iter().flatten().collect(), pairs)
+}
+
+/// A commit in the range that repeats a change from outside its history,
+/// typically a cherry-pick onto a maintenance branch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backport {
+    /// Abbreviated SHA of the commit in the range.
+    pub sha: String,
+    pub subject: String,
+    /// Full SHA of the commit it was picked from.
+    pub original: String,
+    /// Earliest release tag containing the original, if it has shipped.
+    pub released_in: Option<String>,
+    /// Picked from an older release line, like a maintenance fix brought
+    /// forward to `main`, rather than backported from a newer one.
+    pub forward_port: bool,
+}
+
+/// Commits in `from..to` that are cherry-picks, found from their
+/// `(cherry picked from commit ...)` trailer or, failing that, by matching
+/// patch-ids against commits on release tags outside the range's history.
+///
+/// Which way a commit was picked comes from the release lines on either side:
+/// the original's first release, or the newest tag merged into it, against
+/// the newest tag merged into `to`.
+pub fn backports(
+    repo_root: &Path,
+    from: &str,
+    to: &str,
+    paths: &[String],
+    pattern: Option<&TagPattern>,
+) -> Result<Vec<Backport>> {
+    let trailer_re = Regex::new(r"\(cherry picked from commit ([0-9a-f]{7,40})\)").unwrap();
+    let commits = commits_between(repo_root, from, to, paths)?;
+    let to_sha = resolve_ref(repo_root, to)?;
+    let to_line = newest_version(&tags_sorted(repo_root, Some(&to_sha), pattern)?, pattern);
+
+    // Patch-ids of everything released on other branches, only computed when
+    // some commit lacks a trailer.
+    let mut outside: Option<std::collections::HashMap<String, String>> = None;
+    let mut backports = Vec::new();
+    for commit in &commits {
+        let original = match trailer_re.captures(&commit.body) {
+            Some(caps) => match resolve_commit(repo_root, &caps[1]) {
+                Some(sha) => sha,
+                None => continue,
+            },
+            None => {
+                if outside.is_none() {
+                    outside = Some(outside_patch_ids(repo_root, &to_sha, pattern)?);
+                }
+                let outside = outside.as_ref().unwrap();
+                if outside.is_empty() {
+                    continue;
+                }
+                let sha = resolve_ref(repo_root, &commit.sha)?;
+                match patch_ids(repo_root, &[&format!("{sha}^!")])?
+                    .into_iter()
+                    .find_map(|(id, _)| outside.get(&id).cloned())
+                {
+                    Some(original) => original,
+                    None => continue,
+                }
+            }
+        };
+        let released_in = first_release_containing(repo_root, &original, pattern)?;
+        let original_line = match released_in
+            .as_deref()
+            .and_then(|t| tags::tag_version(t, pattern))
+        {
+            Some(version) => Some(version),
+            None => newest_version(&tags_sorted(repo_root, Some(&original), pattern)?, pattern),
+        };
+        backports.push(Backport {
+            sha: commit.sha.clone(),
+            subject: commit.subject.clone(),
+            released_in,
+            forward_port: original_line
+                .zip(to_line.as_ref())
+                .is_some_and(|(o, t)| o < *t),
+            original,
+        });
+    }
+    Ok(backports)
+}
+
+fn newest_version(tags: &[String], pattern: Option<&TagPattern>) -> Option<Version> {
+    tags.iter()
+        .filter_map(|t| tags::tag_version(t, pattern))
+        .max()
+}
+
+fn resolve_commit(repo_root: &Path, sha: &str) -> Option<String> {
+    process::cmd(
+        "git",
+        [
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{sha}^{{commit}}"),
+        ],
+    )
+    .cwd(repo_root)
+    .stderr_capture()
+    .read()
+    .ok()
+    .map(|s| s.trim().to_string())
+}
+
+/// Patch-id → full SHA for non-merge commits on release tags that `to`
+/// doesn't contain.
+fn outside_patch_ids(
+    repo_root: &Path,
+    to: &str,
+    pattern: Option<&TagPattern>,
+) -> Result<std::collections::HashMap<String, String>> {
+    let stdout = process::cmd("git", ["tag", "--no-merged", to])
         .cwd(repo_root)
         .read()?;
-    Ok(output)
+    let tags: Vec<&str> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .filter(|t| pattern.is_none_or(|p| p.matches(t)))
+        .collect();
+    if tags.is_empty() {
+        return Ok(Default::default());
+    }
+    let exclude = format!("^{to}");
+    let mut revs = vec![exclude.as_str()];
+    revs.extend(tags);
+    Ok(patch_ids(repo_root, &revs)?.into_iter().collect())
+}
+
+/// `(patch-id, sha)` pairs for the non-merge commits selected by `revs`.
+fn patch_ids(repo_root: &Path, revs: &[&str]) -> Result<Vec<(String, String)>> {
+    let mut args = vec!["log", "-p", "--no-merges", "--no-color"];
+    args.extend(revs);
+    let diffs = process::cmd("git", &args).cwd(repo_root).read()?;
+    if diffs.is_empty() {
+        return Ok(vec![]);
+    }
+    let ids = process::cmd("git", ["patch-id", "--stable"])
+        .cwd(repo_root)
+        .stdin_bytes(diffs + "\n")
+        .read()?;
+    Ok(ids
+        .lines()
+        .filter_map(|line| {
+            let (id, sha) = line.split_once(' ')?;
+            Some((id.to_string(), sha.trim().to_string()))
+        })
+        .collect())
 }
 
-pub fn extract_pr_numbers(log: &str) -> Vec<u64> {
-    let re = Regex::new(r"\(#(\d+)\)").unwrap();
-    re.captures_iter(log)
-        .filter_map(|cap| cap[1].parse().ok())
-        .collect()
+/// The lowest-versioned release tag containing `sha`.
+fn first_release_containing(
+    repo_root: &Path,
+    sha: &str,
+    pattern: Option<&TagPattern>,
+) -> Result<Option<String>> {
+    let stdout = process::cmd("git", ["tag", "--contains", sha, "--sort=v:refname"])
+        .cwd(repo_root)
+        .read()?;
+    let tags: Vec<&str> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .filter(|t| pattern.is_none_or(|p| p.matches(t)))
+        .collect();
+    let first = tags
+        .iter()
+        .filter_map(|t| Some((tags::tag_version(t, pattern)?, *t)))
+        .min_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, t)| t)
+        .or(tags.first().copied());
+    Ok(first.map(String::from))
+}
+
+/// PR numbers referenced by `commits`, in order of first mention: GitHub's
+/// `(#123)` in subjects and GitLab's `See merge request group/project!123`
+/// trailers. Bodies aren't searched for `(#123)`, since a squash merge lists
+/// the PR of every commit it squashed there.
+pub fn extract_pr_numbers(commits: &[Commit]) -> Vec<u64> {
+    let subject_re = Regex::new(r"\(#(\d+)\)").unwrap();
+    let trailer_re = Regex::new(r"(?m)^See merge request \S+!(\d+)\s*$").unwrap();
+    let mut numbers: Vec<u64> = Vec::new();
+    for commit in commits {
+        let found = subject_re
+            .captures_iter(&commit.subject)
+            .chain(trailer_re.captures_iter(&commit.body));
+        for cap in found {
+            if let Ok(n) = cap[1].parse()
+                && !numbers.contains(&n)
+            {
+                numbers.push(n);
+            }
+        }
+    }
+    numbers
 }
 
 #[cfg(test)]
@@ -121,26 +526,57 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_owner_repo_ssh() {
-        assert_eq!(
-            parse_owner_repo("git@github.com:jdx/pitchfork.git").unwrap(),
-            "jdx/pitchfork"
-        );
+    fn test_detect_remote() {
+        let repo = crate::test_helpers::TempRepo::new();
+        assert!(detect_remote(repo.path(), None, None).is_err());
+        repo.git(&[
+            "remote",
+            "add",
+            "origin",
+            "git@gitlab.com:group/sub/project.git",
+        ]);
+        let remote = detect_remote(repo.path(), None, None).unwrap();
+        assert_eq!(remote.kind, crate::forge::ForgeKind::GitLab);
+        assert_eq!(remote.path, "group/sub/project");
     }
 
-    #[test]
-    fn test_parse_owner_repo_https() {
-        assert_eq!(
-            parse_owner_repo("https://github.com/jdx/pitchfork.git").unwrap(),
-            "jdx/pitchfork"
-        );
+    fn commit(subject: &str, body: &str) -> Commit {
+        Commit {
+            sha: "abc1234".into(),
+            subject: subject.into(),
+            body: body.into(),
+        }
     }
 
     #[test]
     fn test_extract_pr_numbers() {
-        let log =
-            "abc1234 feat: add feature (#123)\ndef5678 fix: bug (#456)\nghi9012 chore: update deps";
-        assert_eq!(extract_pr_numbers(log), vec![123, 456]);
+        let commits = [
+            commit("feat: add feature (#123)", ""),
+            commit("fix: bug (#456)", ""),
+            commit("chore: update deps", ""),
+        ];
+        assert_eq!(extract_pr_numbers(&commits), vec![123, 456]);
+    }
+
+    #[test]
+    fn test_extract_pr_numbers_ignores_squashed_prs_in_body() {
+        let commits = [commit(
+            "feat: release train (#200)",
+            "* fix: parser (#150)\n* feat: plugins (#151)",
+        )];
+        assert_eq!(extract_pr_numbers(&commits), vec![200]);
+    }
+
+    #[test]
+    fn test_extract_pr_numbers_gitlab_trailer() {
+        let commits = [
+            commit(
+                "Merge branch 'fix' into 'main'",
+                "Fix parser\n\nSee merge request group/sub/proj!78",
+            ),
+            commit("feat: thing (#78)", ""),
+        ];
+        assert_eq!(extract_pr_numbers(&commits), vec![78]);
     }
 
     #[test]
@@ -165,6 +601,17 @@ mod tests {
         assert_eq!(sha.len(), 40);
     }
 
+    #[test]
+    fn test_ref_exists() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("f.txt", "a");
+        repo.commit("first");
+        repo.tag("v1.0.0");
+
+        assert!(ref_exists(repo.path(), "v1.0.0"));
+        assert!(!ref_exists(repo.path(), "v2.0.0"));
+    }
+
     #[test]
     fn test_previous_tag() {
         let repo = crate::test_helpers::TempRepo::new();
@@ -175,7 +622,7 @@ mod tests {
         repo.commit("second");
         repo.tag("v2.0.0");
 
-        let prev = previous_tag(repo.path(), "v2.0.0").unwrap();
+        let prev = previous_tag(repo.path(), "v2.0.0", None, Since::Auto, true).unwrap();
         assert_eq!(prev, "v1.0.0");
     }
 
@@ -187,10 +634,176 @@ mod tests {
         repo.tag("v1.0.0");
 
         // No previous tag — should fall back to root commit SHA
-        let prev = previous_tag(repo.path(), "v1.0.0").unwrap();
+        let prev = previous_tag(repo.path(), "v1.0.0", None, Since::Auto, true).unwrap();
         assert_eq!(prev.len(), 40);
     }
 
+    #[test]
+    fn test_previous_tag_with_pattern() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("f.txt", "a");
+        repo.commit("first");
+        repo.tag("cli-v1.3.0");
+        repo.write_file("f.txt", "b");
+        repo.commit("second");
+        repo.tag("sdk-v2.1.0");
+        repo.write_file("f.txt", "c");
+        repo.commit("third");
+        repo.tag("cli-v1.4.0");
+
+        let pattern = TagPattern::new("cli-v*").unwrap();
+        let prev =
+            previous_tag(repo.path(), "cli-v1.4.0", Some(&pattern), Since::Auto, true).unwrap();
+        assert_eq!(prev, "cli-v1.3.0");
+        let prev = previous_tag(repo.path(), "HEAD", Some(&pattern), Since::Auto, true).unwrap();
+        assert_eq!(prev, "cli-v1.4.0");
+    }
+
+    #[test]
+    fn test_previous_tag_skips_prereleases_for_stable() {
+        let repo = crate::test_helpers::TempRepo::new();
+        for tag in ["v1.9.0", "v1.10.0", "v2.0.0-rc.1", "v2.0.0-rc.2", "v2.0.0"] {
+            repo.write_file("f.txt", tag);
+            repo.commit(tag);
+            repo.tag(tag);
+        }
+
+        let prev = |tag, since| previous_tag(repo.path(), tag, None, since, true).unwrap();
+        assert_eq!(prev("v2.0.0", Since::Auto), "v1.10.0");
+        assert_eq!(prev("v2.0.0", Since::Previous), "v2.0.0-rc.2");
+        assert_eq!(prev("v2.0.0-rc.2", Since::Auto), "v2.0.0-rc.1");
+        assert_eq!(prev("v2.0.0-rc.2", Since::Stable), "v1.10.0");
+        assert_eq!(prev("v2.0.1", Since::Auto), "v2.0.0");
+        assert_eq!(prev("HEAD", Since::Auto), "v2.0.0");
+    }
+
+    #[test]
+    fn test_previous_tag_on_maintenance_branch() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("f.txt", "a");
+        repo.commit("first");
+        repo.tag("v1.8.0");
+        repo.git(&["checkout", "-q", "-b", "v1.8.x"]);
+        repo.write_file("f.txt", "patch");
+        repo.commit("fix: backport");
+        repo.tag("v1.8.1");
+        repo.git(&["checkout", "-q", "-"]);
+        repo.write_file("g.txt", "b");
+        repo.commit("feat: big change");
+        repo.tag("v2.0.0");
+        repo.write_file("g.txt", "c");
+        repo.commit("feat: another");
+        repo.tag("v2.1.0");
+        repo.git(&["checkout", "-q", "v1.8.x"]);
+        repo.write_file("f.txt", "patch 2");
+        repo.commit("fix: another backport");
+
+        let prev = |tag| previous_tag(repo.path(), tag, None, Since::Auto, true).unwrap();
+        // Not v2.1.0: it sorts higher but isn't in the branch's history.
+        assert_eq!(prev("v1.8.2"), "v1.8.1");
+        assert_eq!(prev("HEAD"), "v1.8.1");
+        assert_eq!(prev("v2.1.0"), "v2.0.0");
+        // v1.8.1 is newer than v1.8.0 but was never merged back to main.
+        assert_eq!(prev("v2.0.0"), "v1.8.0");
+    }
+
+    #[test]
+    fn test_pair_reverts() {
+        let commit = |sha: &str, subject: &str, body: &str| Commit {
+            sha: sha.into(),
+            subject: subject.into(),
+            body: body.into(),
+        };
+        let commits = vec![
+            commit("aaa1111", "feat: plugins (#3)", ""),
+            commit("bbb2222", "fix: typo", ""),
+            commit(
+                "ccc3333",
+                "Revert \"feat: plugins (#3)\" (#4)",
+                "This reverts commit aaa1111deadbeefdeadbeefdeadbeefdeadbeef.",
+            ),
+            commit("ddd4444", "feat: themes", ""),
+            commit("eee5555", "Revert \"feat: themes\"", ""),
+            commit("fff6666", "Revert \"Revert \"feat: themes\"\"", ""),
+            commit("ggg7777", "Revert \"feat: something older\"", ""),
+        ];
+        let (kept, reverts) = pair_reverts(commits);
+        let kept: Vec<&str> = kept.iter().map(|c| c.sha.as_str()).collect();
+        // The themes revert was itself reverted, so the feature stays.
+        assert_eq!(kept, ["bbb2222", "ddd4444", "ggg7777"]);
+        let pairs: Vec<(&str, &str)> = reverts
+            .iter()
+            .map(|r| (r.original.sha.as_str(), r.revert.sha.as_str()))
+            .collect();
+        assert_eq!(pairs, [("aaa1111", "ccc3333"), ("eee5555", "fff6666")]);
+    }
+
+    #[test]
+    fn test_backports() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("a.txt", "a\n");
+        repo.write_file("b.txt", "b\n");
+        repo.commit("first");
+        repo.tag("v1.0.0");
+        repo.git(&["checkout", "-q", "-b", "v1.x"]);
+        repo.git(&["checkout", "-q", "-"]);
+        repo.write_file("a.txt", "a\nfix a\n");
+        repo.commit("fix: a");
+        repo.write_file("b.txt", "b\nfix b\n");
+        repo.commit("fix: b");
+        repo.write_file("c.txt", "new\n");
+        repo.commit("feat: c");
+        repo.tag("v2.0.0");
+
+        repo.git(&["checkout", "-q", "v1.x"]);
+        repo.git(&["cherry-pick", "-x", "v2.0.0~2"]);
+        repo.git(&["cherry-pick", "v2.0.0~1"]);
+        repo.write_file("d.txt", "only here\n");
+        repo.commit("fix: d");
+
+        let backports = backports(repo.path(), "v1.0.0", "HEAD", &[], None).unwrap();
+        let subjects: Vec<&str> = backports.iter().map(|b| b.subject.as_str()).collect();
+        assert_eq!(subjects, ["fix: a", "fix: b"]);
+        assert!(
+            backports
+                .iter()
+                .all(|b| b.released_in.as_deref() == Some("v2.0.0"))
+        );
+        let fix_a = resolve_ref(repo.path(), "v2.0.0~2").unwrap();
+        assert_eq!(backports[0].original, fix_a);
+        assert!(backports.iter().all(|b| !b.forward_port));
+    }
+
+    #[test]
+    fn test_backports_forward_port() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("a.txt", "a\n");
+        repo.commit("first");
+        repo.tag("v1.0.0");
+        repo.git(&["checkout", "-q", "-b", "v1.x"]);
+        repo.git(&["checkout", "-q", "-"]);
+        repo.write_file("c.txt", "new\n");
+        repo.commit("feat: c");
+        repo.tag("v2.0.0");
+
+        repo.git(&["checkout", "-q", "v1.x"]);
+        repo.write_file("a.txt", "a\nfix a\n");
+        repo.commit("fix: a");
+        repo.tag("v1.0.1");
+        repo.write_file("b.txt", "b\n");
+        repo.commit("fix: b");
+        repo.git(&["checkout", "-q", "-"]);
+        repo.git(&["cherry-pick", "-x", "v1.0.1"]);
+        repo.git(&["cherry-pick", "-x", "v1.x"]);
+
+        let picks = backports(repo.path(), "v2.0.0", "HEAD", &[], None).unwrap();
+        let subjects: Vec<&str> = picks.iter().map(|b| b.subject.as_str()).collect();
+        assert_eq!(subjects, ["fix: a", "fix: b"]);
+        assert_eq!(picks[0].released_in.as_deref(), Some("v1.0.1"));
+        assert_eq!(picks[1].released_in, None);
+        assert!(picks.iter().all(|b| b.forward_port));
+    }
+
     #[test]
     fn test_log_between() {
         let repo = crate::test_helpers::TempRepo::new();
@@ -201,8 +814,47 @@ mod tests {
         repo.commit("second commit");
         repo.tag("v2.0.0");
 
-        let log = log_between(repo.path(), "v1.0.0", "v2.0.0").unwrap();
+        let log = log_between(repo.path(), "v1.0.0", "v2.0.0", &[]).unwrap();
         assert!(log.contains("second commit"));
         assert!(!log.contains("first commit"));
     }
+
+    #[test]
+    fn test_log_between_with_paths() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("packages/cli/main.rs", "a");
+        repo.commit("first commit");
+        repo.tag("v1.0.0");
+        repo.write_file("packages/cli/main.rs", "b");
+        repo.commit("cli change");
+        repo.write_file("packages/sdk/lib.rs", "b");
+        repo.commit("sdk change");
+
+        let paths = vec!["packages/cli".to_string()];
+        let log = log_between(repo.path(), "v1.0.0", "HEAD", &paths).unwrap();
+        assert!(log.contains("cli change"));
+        assert!(!log.contains("sdk change"));
+        let commits = commits_between(repo.path(), "v1.0.0", "HEAD", &paths).unwrap();
+        assert_eq!(commits.len(), 1);
+    }
+
+    #[test]
+    fn test_commits_between_includes_bodies() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("f.txt", "a");
+        repo.commit("first commit");
+        repo.tag("v1.0.0");
+        repo.write_file("f.txt", "b");
+        repo.commit("feat: second\n\nBREAKING CHANGE: it broke");
+        repo.write_file("f.txt", "c");
+        repo.commit("fix: third");
+
+        let commits = commits_between(repo.path(), "v1.0.0", "HEAD", &[]).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "feat: second");
+        assert_eq!(commits[0].body, "BREAKING CHANGE: it broke");
+        assert_eq!(commits[1].subject, "fix: third");
+        assert_eq!(commits[1].body, "");
+        assert_eq!(commits[1].sha.len(), 7);
+    }
 }
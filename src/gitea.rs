@@ -0,0 +1,347 @@
+//! The Gitea API, which Forgejo and Codeberg share. Its releases, pulls and
+//! issues come back in the same shape as GitHub's, so they deserialize
+//! straight into the [`crate::github`] types.
+
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::forge::Remote;
+use crate::github::{Issue, NewRelease, PullRequest, Release};
+
+pub struct GiteaClient {
+    client: reqwest::Client,
+    token: String,
+    owner: String,
+    repo: String,
+    base_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateRelease<'a> {
+    tag_name: &'a str,
+    name: &'a str,
+    body: &'a str,
+    draft: bool,
+    prerelease: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdateRelease {
+    /// Sent for the same reason as GitHub's: an edit without it may detach a
+    /// draft from its tag.
+    tag_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+impl GiteaClient {
+    /// Client for a repository on a Gitea or Forgejo instance, whose API
+    /// lives at `https://<host>/api/v1`.
+    pub fn for_remote(token: String, remote: &Remote) -> Result<Self> {
+        Self::with_base_url(
+            token,
+            &remote.path,
+            format!("https://{}/api/v1", remote.host),
+        )
+    }
+
+    pub(crate) fn with_base_url(token: String, owner_repo: &str, base_url: String) -> Result<Self> {
+        let (owner, repo) = owner_repo
+            .split_once('/')
+            .ok_or_else(|| Error::Gitea(format!("invalid owner/repo: {owner_repo}")))?;
+        let client = reqwest::Client::builder()
+            .user_agent("communique/0.1")
+            .build()?;
+        Ok(Self {
+            client,
+            token,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            base_url,
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/repos/{}/{}{path}", self.base_url, self.owner, self.repo)
+    }
+
+    async fn get(&self, url: &str) -> Result<reqwest::Response> {
+        crate::retry::retry_request("Gitea API", || {
+            self.client
+                .get(url)
+                .header("Authorization", format!("token {}", self.token))
+                .send()
+        })
+        .await
+    }
+
+    pub async fn get_release_by_tag(&self, tag: &str) -> Result<Option<Release>> {
+        let url = self.api_url(&format!("/releases/tags/{tag}"));
+        let resp = self.get(&url).await?;
+        if resp.status().is_success() {
+            return Ok(Some(resp.json().await?));
+        }
+        if resp.status() != reqwest::StatusCode::NOT_FOUND {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!("GET release {tag}: {status} {body}")));
+        }
+
+        // Drafts only show up in the list.
+        let releases = self.list_recent_releases(10).await?;
+        Ok(releases.into_iter().find(|r| r.tag_name == tag))
+    }
+
+    /// Update a release's title and body, preserving its tag.
+    pub async fn update_release(
+        &self,
+        release_id: u64,
+        tag: &str,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("/releases/{release_id}"));
+        let payload = UpdateRelease {
+            tag_name: tag.to_string(),
+            name: title.map(String::from),
+            body: body.map(String::from),
+        };
+        let resp = crate::retry::retry_request("Gitea API", || {
+            self.client
+                .patch(&url)
+                .header("Authorization", format!("token {}", self.token))
+                .json(&payload)
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!(
+                "PATCH release {release_id}: {status} {body}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Create a release. Gitea works out the latest release itself, so
+    /// `make_latest` has no counterpart here.
+    pub async fn create_release(&self, release: &NewRelease<'_>) -> Result<()> {
+        let url = self.api_url("/releases");
+        let payload = CreateRelease {
+            tag_name: release.tag,
+            name: release.title,
+            body: release.body,
+            draft: release.draft,
+            prerelease: release.prerelease,
+        };
+        let resp = crate::retry::retry_request("Gitea API", || {
+            self.client
+                .post(&url)
+                .header("Authorization", format!("token {}", self.token))
+                .json(&payload)
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!(
+                "POST release {}: {status} {body}",
+                release.tag
+            )));
+        }
+        Ok(())
+    }
+
+    /// The most recent releases, newest first. Gitea pages with `limit`
+    /// where GitHub uses `per_page`.
+    pub async fn list_recent_releases(&self, count: u8) -> Result<Vec<Release>> {
+        let url = self.api_url(&format!("/releases?limit={count}&page=1"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!("GET releases: {status} {body}")));
+        }
+        Ok(resp.json().await?)
+    }
+
+    pub async fn get_pr(&self, number: u64) -> Result<PullRequest> {
+        let url = self.api_url(&format!("/pulls/{number}"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!("GET PR #{number}: {status} {body}")));
+        }
+        Ok(resp.json().await?)
+    }
+
+    /// The PR's diff, from `.diff` rather than an `Accept` header.
+    pub async fn get_pr_diff(&self, number: u64) -> Result<String> {
+        let url = self.api_url(&format!("/pulls/{number}.diff"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!(
+                "GET PR #{number} diff: {status} {body}"
+            )));
+        }
+        Ok(resp.text().await?)
+    }
+
+    pub async fn get_issue(&self, number: u64) -> Result<Issue> {
+        let url = self.api_url(&format!("/issues/{number}"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Gitea(format!(
+                "GET issue #{number}: {status} {body}"
+            )));
+        }
+        Ok(resp.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn setup() -> (MockServer, GiteaClient) {
+        let server = MockServer::start().await;
+        let client =
+            GiteaClient::with_base_url("test-token".into(), "owner/repo", server.uri()).unwrap();
+        (server, client)
+    }
+
+    #[test]
+    fn test_for_remote_api_url() {
+        let remote = Remote::parse("https://codeberg.org/forgejo/forgejo.git", None, None).unwrap();
+        let client = GiteaClient::for_remote("t".into(), &remote).unwrap();
+        assert_eq!(
+            client.api_url("/releases"),
+            "https://codeberg.org/api/v1/repos/forgejo/forgejo/releases"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_release_by_tag() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases/tags/v1.0.0"))
+            .and(header("Authorization", "token test-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 5,
+                "tag_name": "v1.0.0",
+                "name": "Version 1.0.0",
+                "body": "Release notes",
+                "draft": false,
+                "prerelease": false,
+                "author": {"login": "alice"}
+            })))
+            .mount(&server)
+            .await;
+
+        let release = client.get_release_by_tag("v1.0.0").await.unwrap().unwrap();
+        assert_eq!(release.id, 5);
+        assert_eq!(release.body.as_deref(), Some("Release notes"));
+    }
+
+    #[tokio::test]
+    async fn test_get_release_by_tag_finds_draft() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases/tags/v2.0.0"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases"))
+            .and(query_param("limit", "10"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": 9, "tag_name": "v2.0.0", "name": "", "body": "", "draft": true}
+            ])))
+            .mount(&server)
+            .await;
+
+        let release = client.get_release_by_tag("v2.0.0").await.unwrap().unwrap();
+        assert_eq!(release.id, 9);
+        assert!(release.draft);
+    }
+
+    #[tokio::test]
+    async fn test_update_release_preserves_the_tag() {
+        let (server, client) = setup().await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/owner/repo/releases/9"))
+            .and(body_partial_json(
+                json!({"tag_name": "v2.0.0", "body": "Body"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": 9})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client
+            .update_release(9, "v2.0.0", Some("Title"), Some("Body"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_pr_and_diff() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "number": 42,
+                "title": "Add feature",
+                "body": "Description",
+                "user": {"login": "testuser", "id": 3},
+                "labels": [{"id": 1, "name": "enhancement", "color": "00aabb"}]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42.diff"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("diff --git a/f b/f"))
+            .mount(&server)
+            .await;
+
+        let pr = client.get_pr(42).await.unwrap();
+        assert_eq!(pr.title, "Add feature");
+        assert_eq!(pr.user.login, "testuser");
+        assert_eq!(pr.labels[0].name, "enhancement");
+        let diff = client.get_pr_diff(42).await.unwrap();
+        assert!(diff.starts_with("diff --git"));
+    }
+
+    #[tokio::test]
+    async fn test_get_issue() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/issues/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "number": 7,
+                "title": "Bug report",
+                "body": "Something is broken",
+                "state": "closed",
+                "user": {"login": "reporter"},
+                "labels": []
+            })))
+            .mount(&server)
+            .await;
+
+        let issue = client.get_issue(7).await.unwrap();
+        assert_eq!(issue.number, 7);
+        assert_eq!(issue.state, "closed");
+    }
+}
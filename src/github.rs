@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
+use crate::forge::Remote;
 
 pub struct GitHubClient {
     client: reqwest::Client,
@@ -13,11 +14,14 @@ pub struct GitHubClient {
 #[derive(Debug, Deserialize)]
 pub struct Release {
     pub id: u64,
-    #[allow(dead_code)]
     pub tag_name: String,
-    #[allow(dead_code)]
     pub name: Option<String>,
     pub body: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    pub published_at: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,9 +43,58 @@ pub struct Issue {
     pub labels: Vec<Label>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SearchResults {
+    pub total_count: u64,
+    pub items: Vec<SearchItem>,
+}
+
+/// An issue or pull request returned by the search API.
+#[derive(Debug, Deserialize)]
+pub struct SearchItem {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub user: User,
+    pub labels: Vec<Label>,
+    pub closed_at: Option<String>,
+    /// Present only when the item is a pull request.
+    pub pull_request: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct User {
     pub login: String,
+    /// "User", "Bot", or "Organization".
+    #[serde(rename = "type", default)]
+    pub kind: String,
+}
+
+impl User {
+    pub fn is_bot(&self) -> bool {
+        self.kind == "Bot" || self.login.ends_with("[bot]")
+    }
+}
+
+/// An issue comment or a review comment on a pull request.
+#[derive(Debug, Deserialize)]
+pub struct Comment {
+    pub user: User,
+    pub body: Option<String>,
+    pub created_at: String,
+    /// File the comment is attached to; only set for review comments.
+    pub path: Option<String>,
+}
+
+/// A file changed by a pull request.
+#[derive(Debug, Deserialize)]
+pub struct PrFile {
+    pub filename: String,
+    /// "added", "removed", "modified", "renamed", "copied", "changed" or "unchanged".
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+    pub previous_filename: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -49,6 +102,30 @@ pub struct Label {
     pub name: String,
 }
 
+/// A release to create for an existing tag.
+#[derive(Debug)]
+pub struct NewRelease<'a> {
+    pub tag: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub draft: bool,
+    pub prerelease: bool,
+    /// Mark the release as the repository's latest. Otherwise GitHub decides
+    /// by version and date, so a backport to an old line doesn't take over.
+    pub make_latest: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateRelease<'a> {
+    tag_name: &'a str,
+    name: &'a str,
+    body: &'a str,
+    draft: bool,
+    prerelease: bool,
+    /// `"true"` or `"legacy"`; GitHub takes a string here, not a bool.
+    make_latest: &'static str,
+}
+
 #[derive(Debug, Serialize)]
 struct UpdateRelease {
     /// Always sent, never skipped.
@@ -70,6 +147,18 @@ impl GitHubClient {
         Self::with_base_url(token, owner_repo, "https://api.github.com".into())
     }
 
+    /// Client for a repository on github.com or a GitHub Enterprise Server,
+    /// whose API lives at `https://<host>/api/v3` unless `api_url` says
+    /// otherwise.
+    pub fn for_remote(token: String, remote: &Remote, api_url: Option<&str>) -> Result<Self> {
+        let base_url = match api_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None if remote.host == "github.com" => return Self::new(token, &remote.path),
+            None => format!("https://{}/api/v3", remote.host),
+        };
+        Self::with_base_url(token, &remote.path, base_url)
+    }
+
     pub(crate) fn with_base_url(token: String, owner_repo: &str, base_url: String) -> Result<Self> {
         let (owner, repo) = owner_repo
             .split_once('/')
@@ -152,8 +241,47 @@ impl GitHubClient {
         Ok(())
     }
 
+    pub async fn create_release(&self, release: &NewRelease<'_>) -> Result<()> {
+        let url = self.api_url("/releases");
+        let payload = CreateRelease {
+            tag_name: release.tag,
+            name: release.title,
+            body: release.body,
+            draft: release.draft,
+            prerelease: release.prerelease,
+            make_latest: if release.make_latest {
+                "true"
+            } else {
+                "legacy"
+            },
+        };
+        let resp = crate::retry::retry_request("GitHub API", || {
+            self.client
+                .post(&url)
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .json(&payload)
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitHub(format!(
+                "POST release {}: {status} {body}",
+                release.tag
+            )));
+        }
+        Ok(())
+    }
+
     pub async fn list_recent_releases(&self, count: u8) -> Result<Vec<Release>> {
-        let url = self.api_url(&format!("/releases?per_page={count}"));
+        self.list_releases(1, count).await
+    }
+
+    /// One page of releases, newest first. Drafts are included.
+    pub async fn list_releases(&self, page: u32, per_page: u8) -> Result<Vec<Release>> {
+        let url = self.api_url(&format!("/releases?per_page={per_page}&page={page}"));
         let resp = crate::retry::retry_request("GitHub API", || {
             self.client
                 .get(&url)
@@ -208,6 +336,132 @@ impl GitHubClient {
         Ok(resp.json().await?)
     }
 
+    /// Conversation comments on a PR (or issue), oldest first.
+    pub async fn list_issue_comments(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Vec<Comment>> {
+        let url = self.api_url(&format!(
+            "/issues/{number}/comments?per_page={per_page}&page={page}"
+        ));
+        let resp = crate::retry::retry_request("GitHub API", || {
+            self.client
+                .get(&url)
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitHub(format!(
+                "GET comments for #{number}: {status} {body}"
+            )));
+        }
+        Ok(resp.json().await?)
+    }
+
+    /// Review comments attached to lines of a PR's diff, oldest first.
+    pub async fn list_review_comments(
+        &self,
+        number: u64,
+        page: u32,
+        per_page: u8,
+    ) -> Result<Vec<Comment>> {
+        let url = self.api_url(&format!(
+            "/pulls/{number}/comments?per_page={per_page}&page={page}"
+        ));
+        let resp = crate::retry::retry_request("GitHub API", || {
+            self.client
+                .get(&url)
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitHub(format!(
+                "GET review comments for PR #{number}: {status} {body}"
+            )));
+        }
+        Ok(resp.json().await?)
+    }
+
+    /// All files changed by a PR, following pagination past the API's
+    /// default page size. GitHub stops listing at 3000 files.
+    pub async fn list_pr_files(&self, number: u64) -> Result<Vec<PrFile>> {
+        const PER_PAGE: usize = 100;
+        let mut files = Vec::new();
+        for page in 1..=30 {
+            let url = self.api_url(&format!(
+                "/pulls/{number}/files?per_page={PER_PAGE}&page={page}"
+            ));
+            let resp = crate::retry::retry_request("GitHub API", || {
+                self.client
+                    .get(&url)
+                    .bearer_auth(&self.token)
+                    .header("Accept", "application/vnd.github+json")
+                    .send()
+            })
+            .await?;
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                return Err(Error::GitHub(format!(
+                    "GET PR #{number} files: {status} {body}"
+                )));
+            }
+            let batch: Vec<PrFile> = resp.json().await?;
+            let done = batch.len() < PER_PAGE;
+            files.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(files)
+    }
+
+    /// Search issues and PRs in this repository. `query` uses GitHub's
+    /// search syntax; the `repo:` qualifier is added here.
+    pub async fn search_issues(
+        &self,
+        query: &str,
+        page: u32,
+        per_page: u8,
+    ) -> Result<SearchResults> {
+        let q = format!("{query} repo:{}/{}", self.owner, self.repo);
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/search/issues", self.base_url),
+            &[
+                ("q", q.as_str()),
+                ("per_page", &per_page.to_string()),
+                ("page", &page.to_string()),
+            ],
+        )
+        .map_err(|e| Error::GitHub(format!("invalid search URL: {e}")))?;
+        let resp = crate::retry::retry_request("GitHub API", || {
+            self.client
+                .get(url.clone())
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json")
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitHub(format!(
+                "GET search {query:?}: {status} {body}"
+            )));
+        }
+        Ok(resp.json().await?)
+    }
+
     pub async fn get_pr_diff(&self, number: u64) -> Result<String> {
         let url = self.api_url(&format!("/pulls/{number}"));
         let resp = crate::retry::retry_request("GitHub API", || {
@@ -225,16 +479,8 @@ impl GitHubClient {
                 "GET PR #{number} diff: {status} {body}"
             )));
         }
-        let diff = resp.text().await?;
-        // Truncate very large diffs to avoid blowing up context
-        if diff.len() > 50_000 {
-            Ok(format!(
-                "{}...\n\n[diff truncated at 50KB]",
-                &diff[..50_000]
-            ))
-        } else {
-            Ok(diff)
-        }
+        // Returned whole; the get_pr_diff tool pages through it.
+        Ok(resp.text().await?)
     }
 }
 
@@ -242,7 +488,7 @@ impl GitHubClient {
 mod tests {
     use super::*;
     use serde_json::json;
-    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn setup() -> (MockServer, GitHubClient) {
@@ -252,6 +498,34 @@ mod tests {
         (server, client)
     }
 
+    #[test]
+    fn test_for_remote_api_url() {
+        let dotcom =
+            GitHubClient::for_remote("t".into(), &Remote::github("owner/repo"), None).unwrap();
+        assert_eq!(
+            dotcom.api_url("/releases"),
+            "https://api.github.com/repos/owner/repo/releases"
+        );
+
+        let ghes = Remote::parse(
+            "git@github.acme.corp:team/app.git",
+            None,
+            Some("github.acme.corp"),
+        )
+        .unwrap();
+        let client = GitHubClient::for_remote("t".into(), &ghes, None).unwrap();
+        assert_eq!(
+            client.api_url("/releases"),
+            "https://github.acme.corp/api/v3/repos/team/app/releases"
+        );
+        let client =
+            GitHubClient::for_remote("t".into(), &ghes, Some("https://api.acme.corp/")).unwrap();
+        assert_eq!(
+            client.api_url("/releases"),
+            "https://api.acme.corp/repos/team/app/releases"
+        );
+    }
+
     #[test]
     fn test_new_invalid_owner_repo() {
         let result = GitHubClient::new("token".into(), "invalid");
@@ -342,6 +616,37 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_create_release() {
+        let (server, client) = setup().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/owner/repo/releases"))
+            .and(body_partial_json(json!({
+                "tag_name": "v2.0.0-rc.1",
+                "name": "Title",
+                "body": "Body",
+                "draft": false,
+                "prerelease": true,
+                "make_latest": "legacy"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 7})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client
+            .create_release(&NewRelease {
+                tag: "v2.0.0-rc.1",
+                title: "Title",
+                body: "Body",
+                draft: false,
+                prerelease: true,
+                make_latest: false,
+            })
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_list_recent_releases() {
         let (server, client) = setup().await;
@@ -395,7 +700,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_pr_diff_truncation() {
+    async fn test_get_pr_diff_returns_full_diff() {
         let (server, client) = setup().await;
         let large_diff = "x".repeat(100_000);
         Mock::given(method("GET"))
@@ -405,8 +710,98 @@ mod tests {
             .await;
 
         let diff = client.get_pr_diff(42).await.unwrap();
-        assert!(diff.contains("[diff truncated at 50KB]"));
-        assert!(diff.len() < 100_000);
+        assert_eq!(diff.len(), 100_000);
+    }
+
+    #[tokio::test]
+    async fn test_list_pr_comments() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/issues/42/comments"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"user": {"login": "alice", "type": "User"}, "body": "LGTM", "created_at": "2024-01-01T00:00:00Z"},
+                {"user": {"login": "codecov[bot]", "type": "Bot"}, "body": "Coverage", "created_at": "2024-01-01T00:00:00Z"}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"user": {"login": "bob"}, "body": "nit", "created_at": "2024-01-02T00:00:00Z", "path": "src/main.rs"}
+            ])))
+            .mount(&server)
+            .await;
+
+        let comments = client.list_issue_comments(42, 2, 50).await.unwrap();
+        assert_eq!(comments.len(), 2);
+        assert!(!comments[0].user.is_bot());
+        assert!(comments[1].user.is_bot());
+
+        let reviews = client.list_review_comments(42, 1, 50).await.unwrap();
+        assert_eq!(reviews[0].path.as_deref(), Some("src/main.rs"));
+        assert_eq!(reviews[0].user.kind, "");
+    }
+
+    #[tokio::test]
+    async fn test_list_pr_files_follows_pages() {
+        let (server, client) = setup().await;
+        let page1: Vec<_> = (0..100)
+            .map(|i| json!({"filename": format!("src/f{i}.rs"), "status": "modified", "additions": 1, "deletions": 0}))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42/files"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!(page1)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42/files"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"filename": "docs/new.md", "status": "renamed", "additions": 0, "deletions": 0, "previous_filename": "docs/old.md"}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let files = client.list_pr_files(42).await.unwrap();
+        assert_eq!(files.len(), 101);
+        assert_eq!(files[100].previous_filename.as_deref(), Some("docs/old.md"));
+    }
+
+    #[tokio::test]
+    async fn test_search_issues_scopes_to_repo() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .and(query_param(
+                "q",
+                "label:regression is:closed repo:owner/repo",
+            ))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 1,
+                "incomplete_results": false,
+                "items": [{
+                    "number": 7,
+                    "title": "Crash on empty config",
+                    "state": "closed",
+                    "user": {"login": "alice"},
+                    "labels": [{"name": "regression"}],
+                    "closed_at": "2024-03-01T12:00:00Z"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let results = client
+            .search_issues("label:regression is:closed", 1, 20)
+            .await
+            .unwrap();
+        assert_eq!(results.total_count, 1);
+        assert_eq!(results.items[0].number, 7);
+        assert!(results.items[0].pull_request.is_none());
     }
 
     #[tokio::test]
@@ -0,0 +1,482 @@
+//! GitLab's REST API, read into the same types as [`crate::github`] so the
+//! rest of the crate need not care which forge it is talking to.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::forge::Remote;
+use crate::github::{Issue, Label, PullRequest, Release, User};
+
+pub struct GitLabClient {
+    client: reqwest::Client,
+    token: String,
+    /// `https://<host>/api/v4/projects/<url-encoded path>`.
+    project_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlRelease {
+    tag_name: String,
+    name: Option<String>,
+    description: Option<String>,
+    released_at: Option<String>,
+    /// Set while `released_at` is still in the future.
+    #[serde(default)]
+    upcoming_release: bool,
+}
+
+impl From<GlRelease> for Release {
+    fn from(r: GlRelease) -> Self {
+        Release {
+            // GitLab addresses releases by tag and exposes no id.
+            id: 0,
+            tag_name: r.tag_name,
+            name: r.name,
+            body: r.description,
+            draft: false,
+            prerelease: r.upcoming_release,
+            published_at: r.released_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlUser {
+    username: String,
+    #[serde(default)]
+    bot: bool,
+}
+
+impl From<GlUser> for User {
+    fn from(u: GlUser) -> Self {
+        User {
+            login: u.username,
+            kind: if u.bot { "Bot" } else { "User" }.into(),
+        }
+    }
+}
+
+/// A merge request or an issue; both use `iid` for the number shown in the
+/// UI and plain strings for labels.
+#[derive(Debug, Deserialize)]
+struct GlItem {
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    #[serde(default)]
+    state: String,
+    author: GlUser,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+fn labels(names: Vec<String>) -> Vec<Label> {
+    names.into_iter().map(|name| Label { name }).collect()
+}
+
+/// One file of a merge request's diff.
+#[derive(Debug, Deserialize)]
+struct GlDiff {
+    old_path: String,
+    new_path: String,
+    diff: String,
+    #[serde(default)]
+    new_file: bool,
+    #[serde(default)]
+    deleted_file: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateRelease<'a> {
+    tag_name: &'a str,
+    name: &'a str,
+    description: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdateRelease {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Percent-encode a path segment; GitLab wants `group/project` and tags
+/// containing `/` as a single segment.
+fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+impl GitLabClient {
+    /// Client for a project on gitlab.com or a self-managed instance, whose
+    /// API lives at `https://<host>/api/v4` unless `api_url` says otherwise.
+    pub fn for_remote(token: String, remote: &Remote, api_url: Option<&str>) -> Result<Self> {
+        let base_url = match api_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!("https://{}/api/v4", remote.host),
+        };
+        Self::with_base_url(token, &remote.path, base_url)
+    }
+
+    pub(crate) fn with_base_url(token: String, project: &str, base_url: String) -> Result<Self> {
+        if !project.contains('/') {
+            return Err(Error::GitLab(format!("invalid project path: {project}")));
+        }
+        let client = reqwest::Client::builder()
+            .user_agent("communique/0.1")
+            .build()?;
+        Ok(Self {
+            client,
+            token,
+            project_url: format!("{base_url}/projects/{}", encode(project)),
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}{path}", self.project_url)
+    }
+
+    async fn get(&self, url: &str) -> Result<reqwest::Response> {
+        crate::retry::retry_request("GitLab API", || {
+            self.client
+                .get(url)
+                .header("PRIVATE-TOKEN", &self.token)
+                .send()
+        })
+        .await
+    }
+
+    pub async fn get_release_by_tag(&self, tag: &str) -> Result<Option<Release>> {
+        let url = self.api_url(&format!("/releases/{}", encode(tag)));
+        let resp = self.get(&url).await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitLab(format!("GET release {tag}: {status} {body}")));
+        }
+        let release: GlRelease = resp.json().await?;
+        Ok(Some(release.into()))
+    }
+
+    /// Update a release's title and description; GitLab addresses it by tag.
+    pub async fn update_release(
+        &self,
+        tag: &str,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<()> {
+        let url = self.api_url(&format!("/releases/{}", encode(tag)));
+        let payload = UpdateRelease {
+            name: title.map(String::from),
+            description: body.map(String::from),
+        };
+        let resp = crate::retry::retry_request("GitLab API", || {
+            self.client
+                .put(&url)
+                .header("PRIVATE-TOKEN", &self.token)
+                .json(&payload)
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitLab(format!("PUT release {tag}: {status} {body}")));
+        }
+        Ok(())
+    }
+
+    /// Create a release for an existing tag. GitLab has no drafts or
+    /// prereleases, and treats the newest release as the latest.
+    pub async fn create_release(&self, tag: &str, title: &str, body: &str) -> Result<()> {
+        let url = self.api_url("/releases");
+        let payload = CreateRelease {
+            tag_name: tag,
+            name: title,
+            description: body,
+        };
+        let resp = crate::retry::retry_request("GitLab API", || {
+            self.client
+                .post(&url)
+                .header("PRIVATE-TOKEN", &self.token)
+                .json(&payload)
+                .send()
+        })
+        .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitLab(format!(
+                "POST release {tag}: {status} {body}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// The most recent releases, newest first by release date.
+    pub async fn list_recent_releases(&self, count: u8) -> Result<Vec<Release>> {
+        let url = self.api_url(&format!("/releases?per_page={count}&page=1"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitLab(format!("GET releases: {status} {body}")));
+        }
+        let releases: Vec<GlRelease> = resp.json().await?;
+        Ok(releases.into_iter().map(Release::from).collect())
+    }
+
+    pub async fn get_merge_request(&self, iid: u64) -> Result<PullRequest> {
+        let url = self.api_url(&format!("/merge_requests/{iid}"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitLab(format!("GET MR !{iid}: {status} {body}")));
+        }
+        let mr: GlItem = resp.json().await?;
+        Ok(PullRequest {
+            number: mr.iid,
+            title: mr.title,
+            body: mr.description,
+            user: mr.author.into(),
+            labels: labels(mr.labels),
+        })
+    }
+
+    /// The merge request's changes as one unified diff, following
+    /// pagination through every file.
+    pub async fn get_merge_request_diff(&self, iid: u64) -> Result<String> {
+        const PER_PAGE: usize = 100;
+        let mut diff = String::new();
+        for page in 1..=30 {
+            let url = self.api_url(&format!(
+                "/merge_requests/{iid}/diffs?per_page={PER_PAGE}&page={page}"
+            ));
+            let resp = self.get(&url).await?;
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                return Err(Error::GitLab(format!(
+                    "GET MR !{iid} diff: {status} {body}"
+                )));
+            }
+            let files: Vec<GlDiff> = resp.json().await?;
+            let done = files.len() < PER_PAGE;
+            for file in files {
+                let old = if file.new_file {
+                    "/dev/null".to_string()
+                } else {
+                    format!("a/{}", file.old_path)
+                };
+                let new = if file.deleted_file {
+                    "/dev/null".to_string()
+                } else {
+                    format!("b/{}", file.new_path)
+                };
+                diff.push_str(&format!(
+                    "diff --git a/{} b/{}\n--- {old}\n+++ {new}\n{}",
+                    file.old_path, file.new_path, file.diff
+                ));
+                if !diff.ends_with('\n') {
+                    diff.push('\n');
+                }
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(diff)
+    }
+
+    pub async fn get_issue(&self, iid: u64) -> Result<Issue> {
+        let url = self.api_url(&format!("/issues/{iid}"));
+        let resp = self.get(&url).await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::GitLab(format!("GET issue #{iid}: {status} {body}")));
+        }
+        let issue: GlItem = resp.json().await?;
+        Ok(Issue {
+            number: issue.iid,
+            title: issue.title,
+            body: issue.description,
+            state: issue.state,
+            user: issue.author.into(),
+            labels: labels(issue.labels),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PROJECT: &str = "/projects/group%2Fsub%2Fproject";
+
+    async fn setup() -> (MockServer, GitLabClient) {
+        let server = MockServer::start().await;
+        let client =
+            GitLabClient::with_base_url("test-token".into(), "group/sub/project", server.uri())
+                .unwrap();
+        (server, client)
+    }
+
+    #[test]
+    fn test_for_remote_api_url() {
+        let remote =
+            Remote::parse("git@gitlab.example.com:group/sub/project.git", None, None).unwrap();
+        let client = GitLabClient::for_remote("t".into(), &remote, None).unwrap();
+        assert_eq!(
+            client.api_url("/releases"),
+            "https://gitlab.example.com/api/v4/projects/group%2Fsub%2Fproject/releases"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_release_by_tag() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{PROJECT}/releases/cli%2Fv1.0.0")))
+            .and(header("PRIVATE-TOKEN", "test-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tag_name": "cli/v1.0.0",
+                "name": "CLI 1.0.0",
+                "description": "Release notes",
+                "released_at": "2024-05-01T00:00:00Z"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{PROJECT}/releases/v9.9.9")))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let release = client
+            .get_release_by_tag("cli/v1.0.0")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(release.tag_name, "cli/v1.0.0");
+        assert_eq!(release.body.as_deref(), Some("Release notes"));
+        assert!(client.get_release_by_tag("v9.9.9").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_release() {
+        let (server, client) = setup().await;
+        Mock::given(method("PUT"))
+            .and(path(format!("{PROJECT}/releases/v1.0.0")))
+            .and(body_json(json!({"name": "Title", "description": "Body"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client
+            .update_release("v1.0.0", Some("Title"), Some("Body"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_recent_releases() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{PROJECT}/releases")))
+            .and(query_param("per_page", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"tag_name": "v2.0.0", "name": "v2", "description": "Notes 2"},
+                {"tag_name": "v1.0.0", "name": "v1", "description": "Notes 1"},
+            ])))
+            .mount(&server)
+            .await;
+
+        let releases = client.list_recent_releases(3).await.unwrap();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].tag_name, "v2.0.0");
+        assert_eq!(releases[1].body.as_deref(), Some("Notes 1"));
+    }
+
+    #[tokio::test]
+    async fn test_get_merge_request() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{PROJECT}/merge_requests/42")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "iid": 42,
+                "title": "Add feature",
+                "description": "Description",
+                "state": "merged",
+                "author": {"username": "testuser"},
+                "labels": ["enhancement"]
+            })))
+            .mount(&server)
+            .await;
+
+        let mr = client.get_merge_request(42).await.unwrap();
+        assert_eq!(mr.number, 42);
+        assert_eq!(mr.title, "Add feature");
+        assert_eq!(mr.user.login, "testuser");
+        assert_eq!(mr.labels[0].name, "enhancement");
+    }
+
+    #[tokio::test]
+    async fn test_get_merge_request_diff() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{PROJECT}/merge_requests/42/diffs")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"old_path": "src/lib.rs", "new_path": "src/lib.rs", "diff": "@@ -1 +1 @@\n-a\n+b\n"},
+                {"old_path": "NEW.md", "new_path": "NEW.md", "diff": "@@ -0,0 +1 @@\n+hi", "new_file": true},
+            ])))
+            .mount(&server)
+            .await;
+
+        let diff = client.get_merge_request_diff(42).await.unwrap();
+        assert_eq!(
+            diff,
+            "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-a\n+b\n\
+             diff --git a/NEW.md b/NEW.md\n--- /dev/null\n+++ b/NEW.md\n@@ -0,0 +1 @@\n+hi\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_issue() {
+        let (server, client) = setup().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{PROJECT}/issues/7")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "iid": 7,
+                "title": "Bug report",
+                "description": "Something is broken",
+                "state": "opened",
+                "author": {"username": "reporter"},
+                "labels": ["bug"]
+            })))
+            .mount(&server)
+            .await;
+
+        let issue = client.get_issue(7).await.unwrap();
+        assert_eq!(issue.number, 7);
+        assert_eq!(issue.state, "opened");
+        assert_eq!(issue.user.login, "reporter");
+        assert_eq!(issue.labels[0].name, "bug");
+    }
+}
@@ -1,17 +1,25 @@
 mod agent;
+mod bump;
+mod changelog;
 mod cli;
 mod command_effects;
 mod config;
+mod conventional;
 mod error;
+mod forge;
 mod generate;
 mod git;
+mod gitea;
 mod github;
+mod gitlab;
 mod links;
 mod llm;
+mod offline;
 mod output;
 mod prompt;
 mod providers;
 mod retry;
+mod tags;
 mod tools;
 mod usage;
 
@@ -54,10 +62,39 @@ async fn main() -> miette::Result<()> {
         Command::Usage(usage) => usage.run(),
         Command::Sponsors => sponsors(),
         Command::Init { force } => init(force),
+        Command::Bump {
+            tag,
+            prev_tag,
+            repo,
+            package,
+            no_ancestry_warning,
+            llm,
+            model,
+            provider,
+            base_url,
+        } => {
+            bump::run(bump::BumpOptions {
+                tag,
+                prev_tag,
+                repo,
+                package,
+                no_ancestry_warning,
+                llm,
+                model,
+                provider,
+                base_url,
+                config: cli.config,
+            })
+            .await
+        }
         Command::Generate {
             tag,
             prev_tag,
-            github_release,
+            publish_release,
+            create_release,
+            draft,
+            prerelease,
+            make_latest,
             changelog,
             concise,
             dry_run,
@@ -67,11 +104,20 @@ async fn main() -> miette::Result<()> {
             provider,
             base_url,
             output,
+            no_llm,
+            package,
+            since_stable,
+            since_previous,
+            no_ancestry_warning,
         } => {
             generate::run(generate::GenerateOptions {
                 tag,
                 prev_tag,
-                github_release,
+                publish_release,
+                create_release,
+                draft,
+                prerelease,
+                make_latest,
                 changelog,
                 concise,
                 dry_run,
@@ -81,6 +127,16 @@ async fn main() -> miette::Result<()> {
                 provider,
                 base_url,
                 output,
+                no_llm,
+                package,
+                since: if since_stable {
+                    tags::Since::Stable
+                } else if since_previous {
+                    tags::Since::Previous
+                } else {
+                    tags::Since::Auto
+                },
+                no_ancestry_warning,
                 config: cli.config,
             })
             .await
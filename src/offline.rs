@@ -0,0 +1,359 @@
+//! Release notes assembled from commit subjects and PR metadata, for
+//! `generate --no-llm`.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::conventional;
+use crate::forge::Remote;
+use crate::git::Commit;
+use crate::github::PullRequest;
+use crate::output::ParsedOutput;
+
+static PR_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^Merge pull request #(\d+)|\(#(\d+)\)\s*$").unwrap());
+static MR_TRAILER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^See merge request \S+!(\d+)\s*$").unwrap());
+
+/// Keep a Changelog categories, in the order they are rendered.
+const CATEGORIES: &[&str] = &[
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+/// Where a change goes, or `None` for internal work that is left out.
+type Category = Option<&'static str>;
+
+/// A single user-facing change.
+#[derive(Debug)]
+struct Entry {
+    category: &'static str,
+    text: String,
+    sha: String,
+    pr: Option<u64>,
+    author: Option<String>,
+    breaking: bool,
+}
+
+/// Range being released, for links.
+pub struct Release<'a> {
+    pub remote: &'a Remote,
+    pub tag: &'a str,
+    pub prev_tag: &'a str,
+}
+
+/// PR number referenced by a squash-merge `(#123)` suffix or a merge commit.
+pub fn pr_number(subject: &str) -> Option<u64> {
+    let caps = PR_REF_RE.captures(subject)?;
+    caps.get(1).or(caps.get(2))?.as_str().parse().ok()
+}
+
+/// PR number referenced by a commit's subject or, for a GitLab merge
+/// commit, its `See merge request group/project!123` trailer.
+pub fn commit_pr_number(commit: &Commit) -> Option<u64> {
+    pr_number(&commit.subject).or_else(|| {
+        MR_TRAILER_RE
+            .captures(&commit.body)
+            .and_then(|caps| caps[1].parse().ok())
+    })
+}
+
+pub fn build(
+    commits: &[Commit],
+    prs: &HashMap<u64, PullRequest>,
+    release: &Release,
+) -> ParsedOutput {
+    let entries: Vec<Entry> = commits.iter().filter_map(|c| entry(c, prs)).collect();
+
+    let mut changelog = Vec::new();
+    let mut body = Vec::new();
+    let mut counts = Vec::new();
+
+    let breaking: Vec<&Entry> = entries.iter().filter(|e| e.breaking).collect();
+    for category in CATEGORIES {
+        let items: Vec<&Entry> = entries.iter().filter(|e| e.category == *category).collect();
+        if items.is_empty() {
+            continue;
+        }
+        counts.push(format!("{} {}", items.len(), category.to_lowercase()));
+        changelog.push(section(category, &items, |e| changelog_line(e, release)));
+        body.push(section(category, &items, |e| body_line(e, release)));
+    }
+    if !breaking.is_empty() {
+        body.push(section("Breaking Changes", &breaking, |e| {
+            body_line(e, release)
+        }));
+    }
+
+    let compare = format!(
+        "**Full Changelog**: {}",
+        release.remote.compare_url(release.prev_tag, release.tag)
+    );
+    let (release_title, release_body) = if entries.is_empty() {
+        (
+            "maintenance release".to_string(),
+            format!("No user-facing changes in this release.\n\n{compare}"),
+        )
+    } else {
+        (
+            counts.join(", "),
+            format!("{}\n\n{compare}", body.join("\n\n")),
+        )
+    };
+
+    ParsedOutput {
+        changelog: changelog.join("\n\n"),
+        release_title,
+        release_body,
+        usage: crate::llm::Usage::default(),
+    }
+}
+
+fn entry(commit: &Commit, prs: &HashMap<u64, PullRequest>) -> Option<Entry> {
+    let number = commit_pr_number(commit);
+    let pr = number.and_then(|n| prs.get(&n));
+
+    // Bare merge commits say nothing unless we know the PR behind them.
+    if commit.subject.starts_with("Merge ") && pr.is_none() {
+        return None;
+    }
+
+    let subject = pr.map_or(commit.subject.as_str(), |pr| pr.title.as_str());
+    let parsed = conventional::parse(subject, &commit.body);
+    let labels: Vec<&str> = pr
+        .map(|pr| pr.labels.iter().map(|l| l.name.as_str()).collect())
+        .unwrap_or_default();
+
+    let category = match labels.iter().find_map(|l| label_category(l)) {
+        Some(category) => category?,
+        None => match &parsed {
+            // A breaking change is worth listing whatever its type.
+            Some(cc) if cc.breaking => type_category(&cc.kind).unwrap_or("Changed"),
+            Some(cc) => type_category(&cc.kind)?,
+            None => "Changed",
+        },
+    };
+
+    let text = match &parsed {
+        Some(cc) => cc.description.clone(),
+        None => subject.to_string(),
+    };
+    let text = strip_pr_suffix(&text);
+    let mut chars = text.chars();
+    let text = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => return None,
+    };
+
+    Some(Entry {
+        category,
+        text,
+        sha: commit.sha.clone(),
+        pr: number,
+        author: pr.map(|pr| pr.user.login.clone()),
+        breaking: parsed.as_ref().is_some_and(|cc| cc.breaking)
+            || labels.iter().any(|l| l.eq_ignore_ascii_case("breaking")),
+    })
+}
+
+/// Category for a PR label, `Some(None)` to omit the change, or `None` when
+/// the label says nothing about it.
+fn label_category(label: &str) -> Option<Category> {
+    let label = label.to_lowercase();
+    let label = label.rsplit([':', '/']).next().unwrap_or(&label).trim();
+    Some(match label {
+        "feature" | "enhancement" | "feat" => Some("Added"),
+        "bug" | "fix" | "bugfix" | "regression" => Some("Fixed"),
+        "security" => Some("Security"),
+        "deprecation" | "deprecated" => Some("Deprecated"),
+        "removal" | "removed" => Some("Removed"),
+        "chore" | "ci" | "internal" | "dependencies" | "skip-changelog" | "no-changelog" => None,
+        _ => return None,
+    })
+}
+
+/// Category for a Conventional Commit type; types that aren't known to be
+/// internal count as user-facing changes.
+fn type_category(kind: &str) -> Category {
+    match kind {
+        "feat" => Some("Added"),
+        "fix" => Some("Fixed"),
+        "security" => Some("Security"),
+        "deprecate" => Some("Deprecated"),
+        "remove" => Some("Removed"),
+        "chore" | "ci" | "build" | "test" | "style" | "refactor" | "docs" | "perf" => None,
+        _ => Some("Changed"),
+    }
+}
+
+fn strip_pr_suffix(text: &str) -> &str {
+    match text.rfind(" (#") {
+        Some(pos) if text.ends_with(')') => text[..pos].trim_end(),
+        _ => text.trim(),
+    }
+}
+
+fn section(heading: &str, entries: &[&Entry], line: impl Fn(&Entry) -> String) -> String {
+    let lines: Vec<String> = entries.iter().map(|e| line(e)).collect();
+    format!("## {heading}\n\n{}", lines.join("\n"))
+}
+
+fn changelog_line(entry: &Entry, release: &Release) -> String {
+    let link = match entry.pr {
+        Some(n) => format!(
+            "[{}]({})",
+            release.remote.pr_ref(n),
+            release.remote.pr_url(n)
+        ),
+        None => format!("[{}]({})", entry.sha, release.remote.commit_url(&entry.sha)),
+    };
+    format!("- {} ({link})", entry.text)
+}
+
+fn body_line(entry: &Entry, release: &Release) -> String {
+    let mut line = format!("- {}", entry.text);
+    match entry.pr {
+        Some(n) => line.push_str(&format!(" ({})", release.remote.pr_ref(n))),
+        None => line.push_str(&format!(" ({})", entry.sha)),
+    }
+    if let Some(author) = &entry.author {
+        line.push_str(&format!(" by @{author}"));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Label, User};
+
+    fn commit(sha: &str, subject: &str) -> Commit {
+        Commit {
+            sha: sha.into(),
+            subject: subject.into(),
+            body: String::new(),
+        }
+    }
+
+    fn release() -> Release<'static> {
+        static REMOTE: std::sync::LazyLock<Remote> =
+            std::sync::LazyLock::new(|| Remote::github("jdx/communique"));
+        Release {
+            remote: &REMOTE,
+            tag: "v1.1.0",
+            prev_tag: "v1.0.0",
+        }
+    }
+
+    #[test]
+    fn test_pr_number() {
+        assert_eq!(pr_number("feat: thing (#12)"), Some(12));
+        assert_eq!(pr_number("Merge pull request #34 from a/b"), Some(34));
+        assert_eq!(pr_number("fix #56 in parser"), None);
+
+        let merge = Commit {
+            body: "Add plugins\n\nSee merge request group/proj!78".into(),
+            ..commit("aaa1111", "Merge branch 'plugins' into 'main'")
+        };
+        assert_eq!(commit_pr_number(&merge), Some(78));
+    }
+
+    #[test]
+    fn test_build_from_conventional_commits() {
+        let commits = [
+            commit("aaa1111", "feat(cli): add --no-llm flag"),
+            commit("bbb2222", "fix: handle empty tags (#7)"),
+            commit("ccc3333", "chore: bump deps"),
+            commit("ddd4444", "Reword help text"),
+            commit("eee5555", "Merge branch 'main' into topic"),
+        ];
+        let parsed = build(&commits, &HashMap::new(), &release());
+
+        assert_eq!(parsed.release_title, "1 added, 1 changed, 1 fixed");
+        assert_eq!(
+            parsed.changelog,
+            "## Added\n\n- Add --no-llm flag ([aaa1111](https://github.com/jdx/communique/commit/aaa1111))\n\n\
+             ## Changed\n\n- Reword help text ([ddd4444](https://github.com/jdx/communique/commit/ddd4444))\n\n\
+             ## Fixed\n\n- Handle empty tags ([#7](https://github.com/jdx/communique/pull/7))"
+        );
+        assert!(
+            parsed
+                .release_body
+                .contains("## Fixed\n\n- Handle empty tags (#7)")
+        );
+        assert!(parsed.release_body.ends_with(
+            "**Full Changelog**: https://github.com/jdx/communique/compare/v1.0.0...v1.1.0"
+        ));
+        assert!(!parsed.release_body.contains("bump deps"));
+    }
+
+    #[test]
+    fn test_build_prefers_pr_title_and_labels() {
+        let commits = [commit("aaa1111", "Merge pull request #9 from alice/topic")];
+        let prs = HashMap::from([(
+            9,
+            PullRequest {
+                number: 9,
+                title: "Support GitLab remotes".into(),
+                body: None,
+                user: User {
+                    login: "alice".into(),
+                    kind: "User".into(),
+                },
+                labels: vec![
+                    Label {
+                        name: "type: enhancement".into(),
+                    },
+                    Label {
+                        name: "breaking".into(),
+                    },
+                ],
+            },
+        )]);
+        let parsed = build(&commits, &prs, &release());
+        assert_eq!(parsed.release_title, "1 added");
+        assert!(parsed.release_body.starts_with(
+            "## Added\n\n- Support GitLab remotes (#9) by @alice\n\n\
+             ## Breaking Changes\n\n- Support GitLab remotes (#9) by @alice"
+        ));
+    }
+
+    #[test]
+    fn test_build_skips_internal_types() {
+        let commits = [
+            commit("aaa1111", "refactor: split parser"),
+            commit("bbb2222", "docs: fix typo"),
+            commit("ccc3333", "perf: cache tag list"),
+            commit("ddd4444", "test: cover merges"),
+            commit("eee5555", "build: bump msrv"),
+            commit("fff6666", "ci: cache"),
+            commit("ggg7777", "ux: clearer errors"),
+            commit("hhh8888", "refactor!: rename config keys"),
+        ];
+        let parsed = build(&commits, &HashMap::new(), &release());
+        assert_eq!(parsed.release_title, "2 changed");
+        assert_eq!(
+            parsed.changelog,
+            "## Changed\n\n- Clearer errors ([ggg7777](https://github.com/jdx/communique/commit/ggg7777))\n\
+             - Rename config keys ([hhh8888](https://github.com/jdx/communique/commit/hhh8888))"
+        );
+    }
+
+    #[test]
+    fn test_build_with_no_user_facing_changes() {
+        let parsed = build(
+            &[commit("aaa1111", "ci: cache")],
+            &HashMap::new(),
+            &release(),
+        );
+        assert_eq!(parsed.release_title, "maintenance release");
+        assert!(parsed.release_body.starts_with("No user-facing changes"));
+        assert_eq!(parsed.changelog, "");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::forge::Remote;
+
 pub fn system_prompt(
     extra: Option<&str>,
     emoji: bool,
@@ -7,14 +9,22 @@ pub fn system_prompt(
     let mut prompt = r#"You are an expert technical writer generating release notes for a software project.
 
 You have access to tools to browse the repository:
-- read_file: Read contents of a git-tracked file (path relative to repo root)
+- read_file: Read contents of a git-tracked file (path relative to repo root) as of the release tag, or at another `ref`
 - list_files: List tracked files, optionally filtered by glob
-- grep: Search file contents with ripgrep
+- grep: Search tracked file contents with git grep (POSIX extended regex), as of the release tag or another `ref`
 - get_pr: Fetch GitHub PR details (title, body, labels, author)
+- get_pr_comments: Fetch the discussion and review comments on a GitHub PR (bots skipped)
+- get_pr_files: List the files a GitHub PR changed, with line counts
 - get_pr_diff: Fetch the diff for a GitHub PR
 - get_issue: Fetch GitHub issue details (title, body, labels, state)
+- search_github: Search this repo's GitHub issues and PRs with search qualifiers (e.g. is:issue label:bug)
+- get_release: Fetch a past GitHub release's notes by tag
+- list_releases: List past GitHub releases (paginated)
+- get_changelog_entry: Read a past version's section from CHANGELOG.md
 - git_show: Show full details of a commit (message, author, diff)
 - get_commits: List commits between refs or for a specific file path
+- diff_range: Show the combined diff stat (and optionally patch) for the whole release range, filterable by path
+- dependency_changes: List dependencies added, removed and upgraded in lockfiles over the release range, flagging semver-major upgrades
 
 Use these tools to understand what changed and why. Read relevant source files, PR descriptions, and diffs to write accurate, insightful release notes.
 
@@ -108,28 +118,42 @@ Be honest about the scope of a release. If it only has one or two user-facing ch
 pub struct UserPromptContext<'a> {
     pub tag: &'a str,
     pub prev_tag: &'a str,
-    pub owner_repo: &'a str,
+    pub remote: &'a Remote,
     pub git_log: &'a str,
+    /// Conventional Commit table to show in place of the flat git log.
+    pub commit_table: Option<&'a str>,
     pub pr_numbers: &'a [u64],
     pub is_unreleased_head: bool,
     pub changelog_entry: Option<&'a str>,
     pub existing_release: Option<&'a str>,
     pub context: Option<&'a str>,
     pub recent_releases: &'a [(String, String)],
+    /// Monorepo package being released, and the paths the log was limited to.
+    pub package: Option<&'a str>,
+    pub package_paths: &'a [String],
+    /// Commits in the range that repeat changes released elsewhere.
+    pub backports: &'a [crate::git::Backport],
+    /// Changes made and reverted within the range, already left out of the log.
+    pub reverts: &'a [crate::git::Revert],
 }
 
 pub fn user_prompt(ctx: &UserPromptContext) -> String {
     let UserPromptContext {
         tag,
         prev_tag,
-        owner_repo,
+        remote,
         git_log,
+        commit_table,
         pr_numbers,
         is_unreleased_head,
         changelog_entry,
         existing_release,
         context,
         recent_releases,
+        package,
+        package_paths,
+        backports,
+        reverts,
     } = ctx;
     let mut parts = Vec::new();
 
@@ -142,21 +166,102 @@ pub fn user_prompt(ctx: &UserPromptContext) -> String {
     } else {
         format!("Generate release notes for **{tag}** (previous release: {prev_tag}).")
     };
+    let release_request = match package {
+        Some(name) => format!(
+            "{release_request}\n\
+             This release covers only the `{name}` package of a monorepo. The git log below is limited to commits touching {}; describe only changes to this package.",
+            package_paths
+                .iter()
+                .map(|p| format!("`{p}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        None => release_request,
+    };
 
+    let log_section = match commit_table {
+        Some(table) => format!(
+            "## Commits\nThe git log, grouped by Conventional Commit type. Every breaking change listed here must be called out in the notes.\n\n{table}"
+        ),
+        None => format!("## Git Log\n```\n{git_log}\n```"),
+    };
+    // The system prompt shows GitHub links; anywhere else needs its own.
+    let links = if remote.host == "github.com" {
+        String::new()
+    } else {
+        format!(
+            "\nThis repository is hosted on {} ({}), not github.com. Use these link formats instead of GitHub's: pull/merge requests `{}` (referenced as `{}`), issues `{}`, commits `{}`, full changelog `{}`.",
+            remote.kind,
+            remote.host,
+            remote.pr_url(123),
+            remote.pr_ref(123),
+            remote.issue_url(123),
+            remote.commit_url("abc1234"),
+            remote.compare_url(prev_tag, tag),
+        )
+    };
     parts.push(format!(
         "{release_request}\n\
-         Repository: `{owner_repo}` (https://github.com/{owner_repo})\n\n\
-         ## Git Log\n```\n{git_log}\n```"
+         Repository: `{}` ({}){links}\n\n\
+         {log_section}",
+        remote.path,
+        remote.web_url(),
     ));
 
+    let pick_lines = |forward_port: bool| {
+        backports
+            .iter()
+            .filter(|b| b.forward_port == forward_port)
+            .map(|b| {
+                let original = b.original.get(..7).unwrap_or(&b.original);
+                match &b.released_in {
+                    Some(tag) => format!(
+                        "- `{}` {} — picked from `{original}`, first released in {tag}",
+                        b.sha, b.subject
+                    ),
+                    None => format!("- `{}` {} — picked from `{original}`", b.sha, b.subject),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let lines = pick_lines(false);
+    if !lines.is_empty() {
+        parts.push(format!(
+            "\n## Backports\nThese commits are cherry-picks of changes made outside this release's history. Present them as backports (e.g. \"backported from v2.3.0\") rather than as new changes:\n{lines}"
+        ));
+    }
+    let lines = pick_lines(true);
+    if !lines.is_empty() {
+        parts.push(format!(
+            "\n## Forward ports\nThese commits bring forward changes from an older release line. They are new to this line, so list them as usual, but don't call them backports; mention the older release where it helps (e.g. \"also released in v1.8.2\"):\n{lines}"
+        ));
+    }
+
+    if !reverts.is_empty() {
+        let lines = reverts
+            .iter()
+            .map(|r| {
+                format!(
+                    "- `{}` {} (reverted by `{}`)",
+                    r.original.sha, r.original.subject, r.revert.sha
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        parts.push(format!(
+            "\n## Reverted Within This Release\nThese changes were made and then reverted before the release, so they are net-zero and have been left out of the log above. Do not list them as shipped:\n{lines}"
+        ));
+    }
+
     if !pr_numbers.is_empty() {
         let prs = pr_numbers
             .iter()
-            .map(|n| format!("#{n}"))
+            .map(|n| remote.pr_ref(*n))
             .collect::<Vec<_>>()
             .join(", ");
         parts.push(format!(
-            "\n## Referenced PRs\n{prs}\n\nUse the `get_pr` and `get_pr_diff` tools to understand these changes in detail."
+            "\n## Referenced PRs\n{prs}\n\nUse the `get_pr` tool to understand these changes, `get_pr_files` to see what they touched, and `get_pr_diff` for detail."
         ));
     }
 
@@ -252,14 +357,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "v1.0.0",
             prev_tag: "v0.9.0",
-            owner_repo: "jdx/communique",
+            remote: &Remote::github("jdx/communique"),
             git_log: "abc1234 feat: add feature",
+            commit_table: None,
             pr_numbers: &[],
             is_unreleased_head: false,
             changelog_entry: None,
             existing_release: None,
             context: None,
             recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
         assert!(prompt.contains("v1.0.0"));
         assert!(prompt.contains("v0.9.0"));
@@ -270,19 +380,193 @@ mod tests {
         assert!(!prompt.contains("Style Reference"));
     }
 
+    #[test]
+    fn test_user_prompt_with_package() {
+        let paths = vec!["packages/cli".to_string(), "crates/shared".to_string()];
+        let prompt = user_prompt(&UserPromptContext {
+            tag: "cli-v1.4.0",
+            prev_tag: "cli-v1.3.0",
+            remote: &Remote::github("jdx/communique"),
+            git_log: "abc1234 feat(cli): add flag",
+            commit_table: None,
+            pr_numbers: &[],
+            is_unreleased_head: false,
+            changelog_entry: None,
+            existing_release: None,
+            context: None,
+            recent_releases: &[],
+            package: Some("cli"),
+            package_paths: &paths,
+            backports: &[],
+            reverts: &[],
+        });
+        assert!(prompt.contains(
+            "only the `cli` package of a monorepo. The git log below is limited to commits touching `packages/cli`, `crates/shared`"
+        ));
+    }
+
+    #[test]
+    fn test_user_prompt_links_for_other_forges() {
+        let github = user_prompt(&UserPromptContext {
+            tag: "v1.0.0",
+            prev_tag: "v0.9.0",
+            remote: &Remote::github("jdx/communique"),
+            git_log: "abc1234 feat: add feature",
+            commit_table: None,
+            pr_numbers: &[],
+            is_unreleased_head: false,
+            changelog_entry: None,
+            existing_release: None,
+            context: None,
+            recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
+        });
+        assert!(
+            github.contains("Repository: `jdx/communique` (https://github.com/jdx/communique)\n")
+        );
+        assert!(!github.contains("link formats"));
+
+        let remote = Remote::parse("git@gitlab.com:group/sub/project.git", None, None).unwrap();
+        let prompt = user_prompt(&UserPromptContext {
+            tag: "v1.0.0",
+            prev_tag: "v0.9.0",
+            remote: &remote,
+            git_log: "abc1234 feat: add feature",
+            commit_table: None,
+            pr_numbers: &[78],
+            is_unreleased_head: false,
+            changelog_entry: None,
+            existing_release: None,
+            context: None,
+            recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
+        });
+        assert!(
+            prompt
+                .contains("Repository: `group/sub/project` (https://gitlab.com/group/sub/project)")
+        );
+        assert!(prompt.contains(
+            "pull/merge requests `https://gitlab.com/group/sub/project/-/merge_requests/123` (referenced as `!123`)"
+        ));
+        assert!(prompt.contains("## Referenced PRs\n!78\n"));
+        assert!(prompt.contains(
+            "full changelog `https://gitlab.com/group/sub/project/-/compare/v0.9.0...v1.0.0`"
+        ));
+    }
+
+    #[test]
+    fn test_user_prompt_with_backports() {
+        let backports = [crate::git::Backport {
+            sha: "abc1234".into(),
+            subject: "fix: crash on empty config".into(),
+            original: "def5678901234567890".into(),
+            released_in: Some("v2.3.0".into()),
+            forward_port: false,
+        }];
+        let prompt = user_prompt(&UserPromptContext {
+            tag: "v1.8.3",
+            prev_tag: "v1.8.2",
+            remote: &Remote::github("jdx/communique"),
+            git_log: "abc1234 fix: crash on empty config",
+            commit_table: None,
+            pr_numbers: &[],
+            is_unreleased_head: false,
+            changelog_entry: None,
+            existing_release: None,
+            context: None,
+            recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &backports,
+            reverts: &[],
+        });
+        assert!(prompt.contains("## Backports"));
+        assert!(prompt.contains(
+            "- `abc1234` fix: crash on empty config — picked from `def5678`, first released in v2.3.0"
+        ));
+        assert!(!prompt.contains("## Forward ports"));
+    }
+
+    #[test]
+    fn test_user_prompt_with_reverts() {
+        let commit = |sha: &str, subject: &str| crate::git::Commit {
+            sha: sha.into(),
+            subject: subject.into(),
+            body: String::new(),
+        };
+        let reverts = [crate::git::Revert {
+            original: commit("aaa1111", "feat: plugins"),
+            revert: commit("bbb2222", "Revert \"feat: plugins\""),
+        }];
+        let prompt = user_prompt(&UserPromptContext {
+            tag: "v1.1.0",
+            prev_tag: "v1.0.0",
+            remote: &Remote::github("jdx/communique"),
+            git_log: "ccc3333 fix: typo",
+            commit_table: None,
+            pr_numbers: &[],
+            is_unreleased_head: false,
+            changelog_entry: None,
+            existing_release: None,
+            context: None,
+            recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &reverts,
+        });
+        assert!(prompt.contains("## Reverted Within This Release"));
+        assert!(prompt.contains("- `aaa1111` feat: plugins (reverted by `bbb2222`)"));
+    }
+
+    #[test]
+    fn test_user_prompt_with_commit_table() {
+        let prompt = user_prompt(&UserPromptContext {
+            tag: "v1.0.0",
+            prev_tag: "v0.9.0",
+            remote: &Remote::github("jdx/communique"),
+            git_log: "abc1234 feat!: add feature",
+            commit_table: Some("| abc1234 | feat | | add feature | **yes** |"),
+            pr_numbers: &[],
+            is_unreleased_head: false,
+            changelog_entry: None,
+            existing_release: None,
+            context: None,
+            recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
+        });
+        assert!(prompt.contains("## Commits\n"));
+        assert!(prompt.contains("| abc1234 | feat | | add feature | **yes** |"));
+        assert!(!prompt.contains("## Git Log"));
+    }
+
     #[test]
     fn test_user_prompt_with_prs() {
         let prompt = user_prompt(&UserPromptContext {
             tag: "v1.0.0",
             prev_tag: "v0.9.0",
-            owner_repo: "jdx/communique",
+            remote: &Remote::github("jdx/communique"),
             git_log: "abc1234 feat (#42)",
+            commit_table: None,
             pr_numbers: &[42, 99],
             is_unreleased_head: false,
             changelog_entry: None,
             existing_release: None,
             context: None,
             recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
         assert!(prompt.contains("Referenced PRs"));
         assert!(prompt.contains("#42"));
@@ -295,14 +579,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "v2.0.0",
             prev_tag: "v1.0.0",
-            owner_repo: "jdx/communique",
+            remote: &Remote::github("jdx/communique"),
             git_log: "def5678 fix: bug",
+            commit_table: None,
             pr_numbers: &[10],
             is_unreleased_head: false,
             changelog_entry: Some("### Fixed\n- Bug fix"),
             existing_release: Some("Previous release body"),
             context: Some("This is a CLI tool for release notes."),
             recent_releases: &[("v1.0.0".into(), "Release 1.0 notes".into())],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
         assert!(prompt.contains("Project Context"));
         assert!(prompt.contains("CLI tool for release notes"));
@@ -320,14 +609,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "v2.0.0",
             prev_tag: "v1.0.0",
-            owner_repo: "test/repo",
+            remote: &Remote::github("test/repo"),
             git_log: "abc init",
+            commit_table: None,
             pr_numbers: &[],
             is_unreleased_head: false,
             changelog_entry: None,
             existing_release: None,
             context: None,
             recent_releases: &[("v1.0.0".into(), long_body)],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
         assert!(prompt.contains("[truncated]"));
     }
@@ -337,14 +631,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "HEAD",
             prev_tag: "v1.0.0",
-            owner_repo: "test/repo",
+            remote: &Remote::github("test/repo"),
             git_log: "abc1234 feat: draft feature",
+            commit_table: None,
             pr_numbers: &[],
             is_unreleased_head: true,
             changelog_entry: None,
             existing_release: None,
             context: None,
             recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
 
         assert!(prompt.contains("Generate release notes for unreleased changes since v1.0.0."));
@@ -356,14 +655,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "v2.0.0",
             prev_tag: "v1.0.0",
-            owner_repo: "test/repo",
+            remote: &Remote::github("test/repo"),
             git_log: "abc1234 feat: tagged feature",
+            commit_table: None,
             pr_numbers: &[],
             is_unreleased_head: false,
             changelog_entry: None,
             existing_release: None,
             context: None,
             recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
 
         assert!(
@@ -376,14 +680,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "HEAD",
             prev_tag: "v1.0.0",
-            owner_repo: "test/repo",
+            remote: &Remote::github("test/repo"),
             git_log: "abc1234 feat: draft feature",
+            commit_table: None,
             pr_numbers: &[],
             is_unreleased_head: true,
             changelog_entry: Some("### Changed\n- Old draft"),
             existing_release: None,
             context: None,
             recent_releases: &[],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
 
         assert!(prompt.contains("Existing Unreleased CHANGELOG.md Draft"));
@@ -403,14 +712,19 @@ mod tests {
         let prompt = user_prompt(&UserPromptContext {
             tag: "v2.0.0",
             prev_tag: "v1.0.0",
-            owner_repo: "test/repo",
+            remote: &Remote::github("test/repo"),
             git_log: "abc init",
+            commit_table: None,
             pr_numbers: &[],
             is_unreleased_head: false,
             changelog_entry: None,
             existing_release: None,
             context: None,
             recent_releases: &[("v1.0.0".into(), body)],
+            package: None,
+            package_paths: &[],
+            backports: &[],
+            reverts: &[],
         });
         assert!(prompt.contains("[truncated]"));
     }
@@ -0,0 +1,356 @@
+use regex::Regex;
+
+use crate::error::{Error, Result};
+
+/// Which tags belong to the package being released, and where the version
+/// sits inside them.
+///
+/// A pattern with a named `version` group (`^cli-v(?P<version>.+)$`) is a
+/// regex; anything else is a glob whose first `*` is the version
+/// (`cli-v*`). Both must match the whole tag.
+#[derive(Debug, Clone)]
+pub struct TagPattern {
+    regex: Regex,
+}
+
+impl TagPattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let is_regex = pattern.contains("(?P<version>") || pattern.contains("(?<version>");
+        let source = if is_regex {
+            format!("^(?:{pattern})$")
+        } else {
+            glob_to_regex(pattern)
+        };
+        let regex = Regex::new(&source)
+            .map_err(|e| Error::Config(format!("invalid tag_pattern '{pattern}': {e}")))?;
+        if regex.capture_names().all(|n| n != Some("version")) {
+            return Err(Error::Config(format!(
+                "tag_pattern '{pattern}' has no version: use a glob with '*' or a regex with a (?P<version>...) group"
+            )));
+        }
+        Ok(Self { regex })
+    }
+
+    pub fn matches(&self, tag: &str) -> bool {
+        self.regex.is_match(tag)
+    }
+
+    /// The version part of a matching tag (`cli-v1.4.0` → `1.4.0`).
+    pub fn version<'a>(&self, tag: &'a str) -> Option<&'a str> {
+        let caps = self.regex.captures(tag)?;
+        caps.name("version").map(|m| m.as_str())
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let mut seen_star = false;
+    for c in glob.chars() {
+        match c {
+            '*' if !seen_star => {
+                re.push_str("(?P<version>.+)");
+                seen_star = true;
+            }
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    re
+}
+
+/// Drop a monorepo package prefix from a tag-style header: `cli-v1.4.0`,
+/// `sdk/v2.0.0` and `pkg@1.2.0` all keep just the version.
+pub fn strip_package_prefix(value: &str) -> &str {
+    let starts_with_version = |rest: &str, sep: char| {
+        let rest = if sep == '@' {
+            rest
+        } else {
+            match rest.strip_prefix('v') {
+                Some(rest) => rest,
+                None => return false,
+            }
+        };
+        rest.starts_with(|c: char| c.is_ascii_digit())
+    };
+    if !value.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return value;
+    }
+    value
+        .char_indices()
+        .filter(|(i, c)| *i > 0 && matches!(c, '-' | '/' | '@'))
+        .find(|(i, c)| starts_with_version(&value[i + 1..], *c))
+        .map_or(value, |(i, _)| &value[i + 1..])
+}
+
+/// Which earlier tag a release is compared against when `--prev-tag` is not
+/// given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Since {
+    /// The previous stable tag for a stable release, the previous tag of any
+    /// kind for a prerelease.
+    #[default]
+    Auto,
+    Stable,
+    Previous,
+}
+
+impl Since {
+    /// Whether prerelease tags are skipped when releasing `current`.
+    pub fn stable_only(self, current: Option<&Version>) -> bool {
+        match self {
+            Since::Auto => current.is_some_and(|v| !v.is_prerelease()),
+            Since::Stable => true,
+            Since::Previous => false,
+        }
+    }
+}
+
+/// A semantic version parsed from a tag, ordered by semver precedence:
+/// `2.0.0-rc.2 < 2.0.0-rc.10 < 2.0.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<Identifier>,
+}
+
+/// Numeric identifiers sort before alphanumeric ones, as the spec requires.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Identifier {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl Version {
+    /// Parses `1.2.3`, `v1.2.3-rc.1+build.5` and the shorthand `v1.2`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+        let s = s.split_once('+').map_or(s, |(v, _)| v);
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (s, None),
+        };
+        let mut parts = core.split('.');
+        let mut number = |required: bool| match parts.next() {
+            Some(p) if !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()) => p.parse().ok(),
+            None if !required => Some(0),
+            _ => None,
+        };
+        let (major, minor, patch) = (number(true)?, number(true)?, number(false)?);
+        if parts.next().is_some() {
+            return None;
+        }
+        let pre = match pre {
+            Some(pre) => pre
+                .split('.')
+                .map(|id| match id.parse() {
+                    _ if id.is_empty() => None,
+                    Ok(n) if id.bytes().all(|b| b.is_ascii_digit()) => Some(Identifier::Numeric(n)),
+                    _ => Some(Identifier::Alpha(id.to_string())),
+                })
+                .collect::<Option<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// The next major version. A prerelease of `x.0.0` becomes `x.0.0`
+    /// itself, as with the minor and patch variants below.
+    pub fn next_major(&self) -> Self {
+        if self.is_prerelease() && self.minor == 0 && self.patch == 0 {
+            return self.release();
+        }
+        Self::core(self.major + 1, 0, 0)
+    }
+
+    pub fn next_minor(&self) -> Self {
+        if self.is_prerelease() && self.patch == 0 {
+            return self.release();
+        }
+        Self::core(self.major, self.minor + 1, 0)
+    }
+
+    pub fn next_patch(&self) -> Self {
+        if self.is_prerelease() {
+            return self.release();
+        }
+        Self::core(self.major, self.minor, self.patch + 1)
+    }
+
+    fn release(&self) -> Self {
+        Self::core(self.major, self.minor, self.patch)
+    }
+
+    fn core(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        for (i, id) in self.pre.iter().enumerate() {
+            f.write_str(if i == 0 { "-" } else { "." })?;
+            match id {
+                Identifier::Numeric(n) => write!(f, "{n}")?,
+                Identifier::Alpha(s) => f.write_str(s)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => std::cmp::Ordering::Equal,
+                (true, false) => std::cmp::Ordering::Greater,
+                (false, true) => std::cmp::Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The version a tag names: the pattern's `version` part when a pattern is
+/// set, otherwise the tag without any package prefix.
+pub fn tag_version(tag: &str, pattern: Option<&TagPattern>) -> Option<Version> {
+    let version = match pattern {
+        Some(p) => p.version(tag)?,
+        None => strip_package_prefix(tag),
+    };
+    Version::parse(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_pattern() {
+        let p = TagPattern::new("cli-v*").unwrap();
+        assert!(p.matches("cli-v1.4.0"));
+        assert!(!p.matches("sdk-v2.1.0"));
+        assert!(!p.matches("v1.0.0"));
+        assert_eq!(p.version("cli-v1.4.0"), Some("1.4.0"));
+        assert_eq!(p.version("sdk-v2.1.0"), None);
+    }
+
+    #[test]
+    fn test_regex_pattern() {
+        let p = TagPattern::new(r"sdk/v?(?P<version>\d+\.\d+\.\d+)").unwrap();
+        assert!(p.matches("sdk/v2.1.0"));
+        assert!(p.matches("sdk/2.1.0"));
+        assert!(!p.matches("sdk/v2.1.0-extra/x"));
+        assert_eq!(p.version("sdk/v2.1.0"), Some("2.1.0"));
+    }
+
+    #[test]
+    fn test_pattern_without_version() {
+        let err = TagPattern::new("release").unwrap_err();
+        assert!(err.to_string().contains("has no version"), "err: {err}");
+        let err = TagPattern::new("(?P<version>[").unwrap_err();
+        assert!(
+            err.to_string().contains("invalid tag_pattern"),
+            "err: {err}"
+        );
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let v = |s| Version::parse(s).unwrap();
+        assert!(v("v2.0.0-rc.1") < v("v2.0.0-rc.2"));
+        assert!(v("2.0.0-rc.2") < v("2.0.0-rc.10"));
+        assert!(v("2.0.0-rc.10") < v("2.0.0"));
+        assert!(v("2.0.0-alpha") < v("2.0.0-alpha.1"));
+        assert!(v("2.0.0-1") < v("2.0.0-alpha"));
+        assert!(v("1.9.0") < v("1.10.0"));
+        assert_eq!(v("v1.2"), v("1.2.0"));
+        assert_eq!(v("1.2.3+build.5"), v("1.2.3"));
+        assert!(v("2.0.0-beta.1").is_prerelease());
+        assert!(!v("2.0.0").is_prerelease());
+        assert!(Version::parse("latest").is_none());
+        assert!(Version::parse("1").is_none());
+        assert!(Version::parse("1.2.3.4").is_none());
+        assert!(Version::parse("1.2.3-").is_none());
+    }
+
+    #[test]
+    fn test_next_versions() {
+        let next = |s: &str| {
+            let v = Version::parse(s).unwrap();
+            (
+                v.next_major().to_string(),
+                v.next_minor().to_string(),
+                v.next_patch().to_string(),
+            )
+        };
+        assert_eq!(
+            next("1.2.3"),
+            ("2.0.0".into(), "1.3.0".into(), "1.2.4".into())
+        );
+        assert_eq!(
+            next("2.0.0-rc.2"),
+            ("2.0.0".into(), "2.0.0".into(), "2.0.0".into())
+        );
+        assert_eq!(
+            next("2.1.1-beta.1"),
+            ("3.0.0".into(), "2.2.0".into(), "2.1.1".into())
+        );
+        assert_eq!(
+            Version::parse("v1.0.0-rc.1+build").unwrap().to_string(),
+            "1.0.0-rc.1"
+        );
+    }
+
+    #[test]
+    fn test_tag_version() {
+        assert_eq!(tag_version("cli-v1.4.0", None), Version::parse("1.4.0"));
+        assert_eq!(
+            tag_version("pkg@2.0.0-rc.1", None),
+            Version::parse("2.0.0-rc.1")
+        );
+        let p = TagPattern::new("cli-v*").unwrap();
+        assert_eq!(tag_version("cli-v1.4.0", Some(&p)), Version::parse("1.4.0"));
+        assert_eq!(tag_version("sdk-v1.4.0", Some(&p)), None);
+    }
+
+    #[test]
+    fn test_since_stable_only() {
+        let stable = Version::parse("2.0.0").unwrap();
+        let rc = Version::parse("2.0.0-rc.1").unwrap();
+        assert!(Since::Auto.stable_only(Some(&stable)));
+        assert!(!Since::Auto.stable_only(Some(&rc)));
+        assert!(!Since::Auto.stable_only(None));
+        assert!(Since::Stable.stable_only(Some(&rc)));
+        assert!(!Since::Previous.stable_only(Some(&stable)));
+    }
+}
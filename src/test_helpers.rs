@@ -44,6 +44,10 @@ impl TempRepo {
     pub fn tag(&self, name: &str) {
         git(self.path(), &["tag", name]);
     }
+
+    pub fn git(&self, args: &[&str]) {
+        git(self.path(), args);
+    }
 }
 
 fn git(dir: &Path, args: &[&str]) {
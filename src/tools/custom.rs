@@ -0,0 +1,220 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::config::{CustomTool, DEFAULT_TOOL_MAX_OUTPUT, DEFAULT_TOOL_TIMEOUT_SECS};
+use crate::error::{Error, Result};
+use crate::llm::ToolDefinition;
+
+pub fn definition(tool: &CustomTool) -> ToolDefinition {
+    ToolDefinition {
+        name: tool.name.clone(),
+        description: tool.description.clone(),
+        input_schema: tool.input_schema.clone(),
+    }
+}
+
+pub async fn execute(repo_root: &Path, tool: &CustomTool, input: &Value) -> Result<String> {
+    let argv = build_argv(tool, input)?;
+    let name = &tool.name;
+    let timeout = Duration::from_secs(tool.timeout.unwrap_or(DEFAULT_TOOL_TIMEOUT_SECS));
+
+    let child = tokio::process::Command::new(&argv[0])
+        .args(&argv[1..])
+        .current_dir(repo_root)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| Error::Tool(format!("{name}: {}: {e}", argv[0])))?;
+
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            Error::Tool(format!(
+                "{name}: command timed out after {}s",
+                timeout.as_secs()
+            ))
+        })?
+        .map_err(|e| Error::Tool(format!("{name}: {e}")))?;
+
+    if !output.status.success() {
+        return Err(Error::Tool(format!(
+            "{name}: command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok("(no output)".into());
+    }
+
+    let max_output = tool.max_output.unwrap_or(DEFAULT_TOOL_MAX_OUTPUT);
+    if stdout.len() > max_output {
+        let mut end = max_output;
+        while !stdout.is_char_boundary(end) {
+            end -= 1;
+        }
+        Ok(format!(
+            "{}...\n\n[output truncated at {max_output} bytes]",
+            &stdout[..end]
+        ))
+    } else {
+        Ok(stdout.into_owned())
+    }
+}
+
+/// Expand `{field}` placeholders in the command template into argv.
+///
+/// An argument that is exactly one placeholder is dropped when the field is
+/// absent and expands to several arguments when the field is an array, so
+/// optional flags and lists need no quoting. Values are never interpreted by a
+/// shell.
+fn build_argv(tool: &CustomTool, input: &Value) -> Result<Vec<String>> {
+    let re = Regex::new(r"\{([A-Za-z0-9_]+)\}").unwrap();
+    let name = &tool.name;
+    let mut argv = Vec::with_capacity(tool.command.len());
+
+    for arg in &tool.command {
+        if let Some(caps) = re.captures(arg)
+            && caps[0].len() == arg.len()
+        {
+            match &input[&caps[1]] {
+                Value::Null => {}
+                Value::Array(items) => {
+                    for item in items {
+                        argv.push(scalar_to_arg(name, &caps[1], item)?);
+                    }
+                }
+                value => argv.push(scalar_to_arg(name, &caps[1], value)?),
+            }
+            continue;
+        }
+
+        let mut err = None;
+        let expanded = re.replace_all(arg, |caps: &regex::Captures| match &input[&caps[1]] {
+            Value::Null => {
+                err.get_or_insert_with(|| {
+                    Error::Tool(format!("{name}: missing '{}' parameter", &caps[1]))
+                });
+                String::new()
+            }
+            value => scalar_to_arg(name, &caps[1], value).unwrap_or_else(|e| {
+                err.get_or_insert(e);
+                String::new()
+            }),
+        });
+        if let Some(err) = err {
+            return Err(err);
+        }
+        argv.push(expanded.into_owned());
+    }
+
+    if argv.is_empty() {
+        return Err(Error::Tool(format!("{name}: command is empty")));
+    }
+    Ok(argv)
+}
+
+fn scalar_to_arg(tool: &str, field: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(Error::Tool(format!(
+            "{tool}: '{field}' must be a string, number, or boolean"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempRepo;
+    use serde_json::json;
+
+    fn tool(command: &[&str]) -> CustomTool {
+        CustomTool {
+            name: "custom".into(),
+            description: "A custom tool".into(),
+            input_schema: json!({"type": "object"}),
+            command: command.iter().map(|s| s.to_string()).collect(),
+            timeout: None,
+            max_output: None,
+        }
+    }
+
+    #[test]
+    fn test_build_argv_substitutes_without_shell() {
+        let t = tool(&["echo", "--name={name}", "{name}"]);
+        let argv = build_argv(&t, &json!({"name": "a b; rm -rf /"})).unwrap();
+        assert_eq!(argv, ["echo", "--name=a b; rm -rf /", "a b; rm -rf /"]);
+    }
+
+    #[test]
+    fn test_build_argv_optional_and_array_fields() {
+        let t = tool(&["git", "log", "{flags}", "--", "{paths}"]);
+        let argv = build_argv(&t, &json!({"paths": ["src", "docs"]})).unwrap();
+        assert_eq!(argv, ["git", "log", "--", "src", "docs"]);
+    }
+
+    #[test]
+    fn test_build_argv_missing_embedded_field() {
+        let t = tool(&["echo", "--tag={tag}"]);
+        let err = build_argv(&t, &json!({})).unwrap_err();
+        assert!(err.to_string().contains("missing 'tag'"), "err: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_execute_runs_in_repo_root() {
+        let repo = TempRepo::new();
+        repo.write_file("hello.txt", "hi");
+        repo.commit("init");
+
+        let t = tool(&["git", "ls-files"]);
+        let result = execute(repo.path(), &t, &json!({})).await.unwrap();
+        assert!(result.contains("hello.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_truncates_output() {
+        let repo = TempRepo::new();
+        repo.write_file("big.txt", &"x".repeat(1000));
+        repo.commit("init");
+
+        let t = CustomTool {
+            max_output: Some(100),
+            ..tool(&["cat", "{path}"])
+        };
+        let result = execute(repo.path(), &t, &json!({"path": "big.txt"}))
+            .await
+            .unwrap();
+        assert!(result.contains("[output truncated at 100 bytes]"));
+        assert!(result.len() < 1000);
+    }
+
+    #[tokio::test]
+    async fn test_execute_timeout() {
+        let repo = TempRepo::new();
+        let t = CustomTool {
+            timeout: Some(1),
+            ..tool(&["sleep", "5"])
+        };
+        let err = execute(repo.path(), &t, &json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"), "err: {err}");
+    }
+
+    #[tokio::test]
+    async fn test_execute_nonzero_exit() {
+        let repo = TempRepo::new();
+        let t = tool(&["git", "show", "does-not-exist"]);
+        let err = execute(repo.path(), &t, &json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("exited with"), "err: {err}");
+    }
+}
@@ -0,0 +1,483 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde_json::json;
+use xx::process;
+
+use super::ToolContext;
+use crate::error::{Error, Result};
+use crate::git;
+use crate::llm::ToolDefinition;
+use crate::tags::Version;
+
+const DEFAULT_LIMIT: usize = 1000;
+const MAX_BYTES: usize = 50_000;
+
+/// Lockfile names this tool understands, matched at any depth.
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "go.sum",
+    "poetry.lock",
+];
+
+/// Package name → every version of it in the lockfile.
+type Packages = BTreeMap<String, BTreeSet<LockVersion>>;
+
+/// A version from a lockfile. Versions that parse as semver sort by it, so
+/// `1.10.0` comes after `1.9.0`, and anything else sorts after them as a
+/// string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LockVersion(String);
+
+impl Ord for LockVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        match (Version::parse(&self.0), Version::parse(&other.0)) {
+            (Some(a), Some(b)) => a.cmp(&b).then_with(|| self.0.cmp(&other.0)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for LockVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "dependency_changes".into(),
+        description: "Compare lockfiles (Cargo.lock, package-lock.json, pnpm-lock.yaml, go.sum, poetry.lock) between two refs and list added, removed and upgraded packages with old and new versions. Semver-major upgrades are flagged.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "from": {
+                    "type": "string",
+                    "description": "Start ref. Defaults to the previous release tag."
+                },
+                "to": {
+                    "type": "string",
+                    "description": "End ref. Defaults to the release tag."
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Only compare this lockfile (e.g. 'web/package-lock.json'). Defaults to every lockfile in the repo."
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Line to start from, for paging through large output. Defaults to 0."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return. Defaults to 1000."
+                },
+                "byte_offset": {
+                    "type": "integer",
+                    "description": "Byte to resume from within the line at offset, when a single line is too long for one page. Defaults to 0."
+                }
+            }
+        }),
+    }
+}
+
+pub fn execute(ctx: &ToolContext<'_>, input: &serde_json::Value) -> Result<String> {
+    let from = input
+        .get("from")
+        .and_then(|v| v.as_str())
+        .or(ctx.prev_tag)
+        .ok_or_else(|| Error::Tool("dependency_changes: missing 'from' parameter".into()))?;
+    let to = input
+        .get("to")
+        .and_then(|v| v.as_str())
+        .or(ctx.tag)
+        .unwrap_or("HEAD");
+    let only = input.get("path").and_then(|v| v.as_str());
+
+    // resolve_ref falls back to HEAD, which suits an untagged release but
+    // would hide a typo in a ref the model asked for.
+    for key in ["from", "to"] {
+        if let Some(r) = input.get(key).and_then(|v| v.as_str())
+            && !git::ref_exists(ctx.repo_root, r)
+        {
+            return Err(Error::Tool(format!("dependency_changes: unknown ref: {r}")));
+        }
+    }
+    let from_sha = git::resolve_ref(ctx.repo_root, from)?;
+    let to_sha = git::resolve_ref(ctx.repo_root, to)?;
+
+    let mut lockfiles = list_lockfiles(ctx.repo_root, &from_sha)?;
+    lockfiles.extend(list_lockfiles(ctx.repo_root, &to_sha)?);
+    if let Some(only) = only {
+        lockfiles.retain(|p| p == only);
+    }
+    lockfiles.retain(|p| !super::read_file::is_denied(p, &ctx.config.read_deny));
+    if lockfiles.is_empty() {
+        return Ok(match only {
+            Some(only) => format!("{only} is not a supported lockfile at {from} or {to}."),
+            None => format!("No supported lockfiles found at {from} or {to}."),
+        });
+    }
+
+    let mut sections = Vec::new();
+    for path in &lockfiles {
+        let old = parse_lockfile(path, &read_at(ctx.repo_root, &from_sha, path))
+            .map_err(|e| Error::Tool(format!("dependency_changes: {path} at {from}: {e}")))?;
+        let new = parse_lockfile(path, &read_at(ctx.repo_root, &to_sha, path))
+            .map_err(|e| Error::Tool(format!("dependency_changes: {path} at {to}: {e}")))?;
+        if let Some(section) = diff_packages(path, &old, &new) {
+            sections.push(section);
+        }
+    }
+
+    if sections.is_empty() {
+        return Ok(format!("No dependency changes between {from} and {to}."));
+    }
+    let output = sections.join("\n\n");
+    let page = super::page_params(input, DEFAULT_LIMIT);
+    Ok(format!(
+        "Dependency changes {from}..{to}\n\n{}",
+        super::paginate(&output, page, MAX_BYTES)
+    ))
+}
+
+fn list_lockfiles(repo_root: &Path, sha: &str) -> Result<BTreeSet<String>> {
+    let files = process::cmd("git", ["ls-tree", "-r", "--name-only", sha])
+        .cwd(repo_root)
+        .read()?;
+    Ok(files
+        .lines()
+        .filter(|p| LOCKFILES.contains(&p.rsplit('/').next().unwrap_or(p)))
+        .map(String::from)
+        .collect())
+}
+
+/// File contents at `sha`, or empty if it doesn't exist there.
+fn read_at(repo_root: &Path, sha: &str, path: &str) -> String {
+    process::cmd("git", ["show", &format!("{sha}:{path}")])
+        .cwd(repo_root)
+        .stderr_capture()
+        .read()
+        .unwrap_or_default()
+}
+
+fn parse_lockfile(path: &str, contents: &str) -> std::result::Result<Packages, String> {
+    if contents.trim().is_empty() {
+        return Ok(Packages::new());
+    }
+    match path.rsplit('/').next().unwrap_or(path) {
+        // Both are TOML with a `[[package]]` array of name/version tables.
+        "Cargo.lock" | "poetry.lock" => parse_toml_packages(contents),
+        "package-lock.json" => parse_package_lock(contents),
+        "pnpm-lock.yaml" => Ok(parse_pnpm_lock(contents)),
+        "go.sum" => Ok(parse_go_sum(contents)),
+        _ => Ok(Packages::new()),
+    }
+}
+
+fn parse_toml_packages(contents: &str) -> std::result::Result<Packages, String> {
+    let doc: toml::Value = toml::from_str(contents).map_err(|e| e.to_string())?;
+    let mut packages = Packages::new();
+    for pkg in doc
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let (Some(name), Some(version)) = (
+            pkg.get("name").and_then(|v| v.as_str()),
+            pkg.get("version").and_then(|v| v.as_str()),
+        ) {
+            insert(&mut packages, name, version);
+        }
+    }
+    Ok(packages)
+}
+
+fn parse_package_lock(contents: &str) -> std::result::Result<Packages, String> {
+    let doc: serde_json::Value = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    let mut packages = Packages::new();
+
+    // lockfileVersion 2 and 3: flat map keyed by install path.
+    if let Some(map) = doc.get("packages").and_then(|p| p.as_object()) {
+        for (key, pkg) in map {
+            let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                continue; // the root project itself
+            };
+            if pkg.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                insert(&mut packages, name, version);
+            }
+        }
+        return Ok(packages);
+    }
+
+    // lockfileVersion 1: nested `dependencies` trees.
+    fn walk(deps: &serde_json::Value, packages: &mut Packages) {
+        for (name, pkg) in deps.as_object().into_iter().flatten() {
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                insert(packages, name, version);
+            }
+            if let Some(nested) = pkg.get("dependencies") {
+                walk(nested, packages);
+            }
+        }
+    }
+    if let Some(deps) = doc.get("dependencies") {
+        walk(deps, &mut packages);
+    }
+    Ok(packages)
+}
+
+/// Reads package keys from the top-level `packages:` map without a YAML
+/// parser. Handles `/name@1.0.0` (v6), `name@1.0.0` (v9), `/name/1.0.0` (v5)
+/// and peer-dependency suffixes like `name@1.0.0(react@18.0.0)`.
+fn parse_pnpm_lock(contents: &str) -> Packages {
+    let mut packages = Packages::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        if !line.starts_with(' ') && !line.trim().is_empty() {
+            in_packages = line.trim_end() == "packages:";
+            continue;
+        }
+        let Some(key) = line
+            .strip_prefix("  ")
+            .filter(|k| !k.starts_with(' '))
+            .and_then(|k| k.trim_end().strip_suffix(':'))
+        else {
+            continue;
+        };
+        if !in_packages {
+            continue;
+        }
+        let key = key.trim_matches(['\'', '"']);
+        let key = key.strip_prefix('/').unwrap_or(key);
+        let key = key.split_once('(').map_or(key, |(k, _)| k);
+        if key.is_empty() {
+            continue;
+        }
+        let split = match key[1..].rfind('@') {
+            Some(at) => Some((&key[..at + 1], &key[at + 2..])),
+            None => key.rsplit_once('/'),
+        };
+        if let Some((name, version)) = split {
+            insert(&mut packages, name, version);
+        }
+    }
+    packages
+}
+
+fn parse_go_sum(contents: &str) -> Packages {
+    let mut packages = Packages::new();
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        if let (Some(module), Some(version)) = (parts.next(), parts.next())
+            && !version.ends_with("/go.mod")
+        {
+            insert(&mut packages, module, version);
+        }
+    }
+    packages
+}
+
+fn insert(packages: &mut Packages, name: &str, version: &str) {
+    packages
+        .entry(name.to_string())
+        .or_default()
+        .insert(LockVersion(version.to_string()));
+}
+
+fn diff_packages(path: &str, old: &Packages, new: &Packages) -> Option<String> {
+    let mut upgraded = Vec::new();
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+
+    for (name, new_versions) in new {
+        match old.get(name) {
+            None => added.push(format!("  added     {name} {}", join(new_versions))),
+            Some(old_versions) if old_versions != new_versions => {
+                let major = match (old_versions.iter().last(), new_versions.iter().last()) {
+                    (Some(o), Some(n)) => is_major_bump(&o.0, &n.0),
+                    _ => false,
+                };
+                upgraded.push(format!(
+                    "  upgraded  {name} {} -> {}{}",
+                    join(old_versions),
+                    join(new_versions),
+                    if major { " [semver-major]" } else { "" }
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, old_versions) in old {
+        if !new.contains_key(name) {
+            removed.push(format!("  removed   {name} {}", join(old_versions)));
+        }
+    }
+
+    if upgraded.is_empty() && added.is_empty() && removed.is_empty() {
+        return None;
+    }
+    let mut lines = vec![format!(
+        "{path} ({} upgraded, {} added, {} removed)",
+        upgraded.len(),
+        added.len(),
+        removed.len()
+    )];
+    lines.extend(upgraded);
+    lines.extend(added);
+    lines.extend(removed);
+    Some(lines.join("\n"))
+}
+
+fn join(versions: &BTreeSet<LockVersion>) -> String {
+    versions
+        .iter()
+        .map(|v| v.0.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether going from `old` to `new` changes the leftmost non-zero version
+/// component, which is what semver (and Cargo) treat as breaking.
+fn is_major_bump(old: &str, new: &str) -> bool {
+    fn components(v: &str) -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map_while(|c| c.parse().ok())
+            .collect()
+    }
+    let (old, new) = (components(old), components(new));
+    let Some(pos) = old.iter().position(|&c| c != 0) else {
+        return old != new;
+    };
+    old.get(..=pos) != new.get(..=pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempRepo;
+
+    #[test]
+    fn test_is_major_bump() {
+        assert!(is_major_bump("1.9.0", "2.0.0"));
+        assert!(is_major_bump("0.12.3", "0.13.0"));
+        assert!(is_major_bump("v0.0.3", "v0.0.4"));
+        assert!(!is_major_bump("1.2.0", "1.9.0"));
+        assert!(!is_major_bump("0.12.3", "0.12.9"));
+    }
+
+    #[test]
+    fn test_parse_package_lock_v3_and_v1() {
+        let v3 = r#"{"lockfileVersion": 3, "packages": {
+            "": {"name": "app"},
+            "node_modules/react": {"version": "18.2.0"},
+            "node_modules/@types/node": {"version": "20.1.0"},
+            "node_modules/a/node_modules/react": {"version": "17.0.2"}
+        }}"#;
+        let packages = parse_package_lock(v3).unwrap();
+        assert_eq!(join(&packages["react"]), "17.0.2, 18.2.0");
+        assert_eq!(join(&packages["@types/node"]), "20.1.0");
+        assert!(!packages.contains_key("app"));
+
+        let v1 = r#"{"lockfileVersion": 1, "dependencies": {
+            "lodash": {"version": "4.17.21", "dependencies": {"tiny": {"version": "1.0.0"}}}
+        }}"#;
+        let packages = parse_package_lock(v1).unwrap();
+        assert_eq!(packages.len(), 2);
+    }
+
+    #[test]
+    fn test_multi_version_entries_order_by_semver() {
+        let old = parse_toml_packages(
+            "[[package]]\nname = \"syn\"\nversion = \"1.9.0\"\n\n[[package]]\nname = \"syn\"\nversion = \"1.10.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(join(&old["syn"]), "1.9.0, 1.10.0");
+
+        let new = parse_toml_packages(
+            "[[package]]\nname = \"syn\"\nversion = \"1.9.0\"\n\n[[package]]\nname = \"syn\"\nversion = \"2.0.0\"\n",
+        )
+        .unwrap();
+        let section = diff_packages("Cargo.lock", &old, &new).unwrap();
+        assert!(
+            section.contains("upgraded  syn 1.9.0, 1.10.0 -> 1.9.0, 2.0.0 [semver-major]"),
+            "{section}"
+        );
+
+        let mut versions = BTreeSet::new();
+        for v in ["v0.0.0-20240101-abcdef", "weird", "0.10.0", "0.9.1"] {
+            versions.insert(LockVersion(v.into()));
+        }
+        assert_eq!(
+            join(&versions),
+            "v0.0.0-20240101-abcdef, 0.9.1, 0.10.0, weird"
+        );
+    }
+
+    #[test]
+    fn test_parse_pnpm_lock_and_go_sum() {
+        let pnpm = "lockfileVersion: '9.0'\n\nimporters:\n  .:\n    dependencies: {}\n\npackages:\n\n  '@babel/core@7.24.0':\n    resolution: {integrity: x}\n\n  react-dom@18.2.0(react@18.2.0):\n    resolution: {integrity: y}\n\n  /left-pad/1.3.0:\n    resolution: {integrity: z}\n";
+        let packages = parse_pnpm_lock(pnpm);
+        assert_eq!(join(&packages["@babel/core"]), "7.24.0");
+        assert_eq!(join(&packages["react-dom"]), "18.2.0");
+        assert_eq!(join(&packages["left-pad"]), "1.3.0");
+        assert_eq!(packages.len(), 3);
+
+        let go =
+            "github.com/pkg/errors v0.9.1 h1:abc=\ngithub.com/pkg/errors v0.9.1/go.mod h1:def=\n";
+        let packages = parse_go_sum(go);
+        assert_eq!(join(&packages["github.com/pkg/errors"]), "v0.9.1");
+    }
+
+    #[test]
+    fn test_dependency_changes_between_tags() {
+        let repo = TempRepo::new();
+        repo.write_file(
+            "Cargo.lock",
+            "version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.100\"\n\n[[package]]\nname = \"reqwest\"\nversion = \"0.12.5\"\n\n[[package]]\nname = \"old-dep\"\nversion = \"0.1.0\"\n",
+        );
+        repo.commit("first");
+        repo.tag("v1.0.0");
+        repo.write_file(
+            "Cargo.lock",
+            "version = 3\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.200\"\n\n[[package]]\nname = \"reqwest\"\nversion = \"0.13.1\"\n\n[[package]]\nname = \"new-dep\"\nversion = \"2.0.0\"\n",
+        );
+        repo.write_file(
+            "web/package-lock.json",
+            r#"{"packages": {"node_modules/vite": {"version": "5.0.0"}}}"#,
+        );
+        repo.commit("second");
+        repo.tag("v1.1.0");
+
+        let ctx = ToolContext {
+            tag: Some("v1.1.0"),
+            prev_tag: Some("v1.0.0"),
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({})).unwrap();
+        assert!(result.starts_with("Dependency changes v1.0.0..v1.1.0"));
+        assert!(result.contains("Cargo.lock (2 upgraded, 1 added, 1 removed)"));
+        assert!(result.contains("upgraded  reqwest 0.12.5 -> 0.13.1 [semver-major]"));
+        assert!(result.contains("upgraded  serde 1.0.100 -> 1.0.200\n"));
+        assert!(result.contains("added     new-dep 2.0.0"));
+        assert!(result.contains("removed   old-dep 0.1.0"));
+        assert!(result.contains("web/package-lock.json (0 upgraded, 1 added, 0 removed)"));
+
+        let result = execute(&ctx, &json!({"path": "web/package-lock.json"})).unwrap();
+        assert!(!result.contains("Cargo.lock"));
+        let err = execute(&ctx, &json!({"from": "v1.0.O"})).unwrap_err();
+        assert!(err.to_string().contains("unknown ref: v1.0.O"), "{err}");
+    }
+}
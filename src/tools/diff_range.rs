@@ -0,0 +1,209 @@
+use serde_json::json;
+use xx::process;
+
+use super::ToolContext;
+use crate::error::{Error, Result};
+use crate::git;
+use crate::llm::ToolDefinition;
+
+const DEFAULT_LIMIT: usize = 1000;
+const MAX_BYTES: usize = 50_000;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "diff_range".into(),
+        description: "Show the combined diff for the whole release range: a --stat summary (including renames) and, optionally, the patch. Cheaper than git_show on many individual commits.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "from": {
+                    "type": "string",
+                    "description": "Start ref (exclusive). Defaults to the previous release tag."
+                },
+                "to": {
+                    "type": "string",
+                    "description": "End ref (inclusive). Defaults to the release tag."
+                },
+                "paths": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Restrict the diff to these paths or globs (e.g. 'src/cli/', '*.md', 'docs/**/*.md')"
+                },
+                "patch": {
+                    "type": "boolean",
+                    "description": "Include the full patch, not just the stat summary. Defaults to false."
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Line to start from, for paging through large output. Defaults to 0."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return. Defaults to 1000."
+                },
+                "byte_offset": {
+                    "type": "integer",
+                    "description": "Byte to resume from within the line at offset, when a single line is too long for one page. Defaults to 0."
+                }
+            }
+        }),
+    }
+}
+
+pub fn execute(ctx: &ToolContext<'_>, input: &serde_json::Value) -> Result<String> {
+    let from = input
+        .get("from")
+        .and_then(|v| v.as_str())
+        .or(ctx.prev_tag)
+        .ok_or_else(|| Error::Tool("diff_range: missing 'from' parameter".into()))?;
+    let to = input
+        .get("to")
+        .and_then(|v| v.as_str())
+        .or(ctx.tag)
+        .unwrap_or("HEAD");
+    let patch = input
+        .get("patch")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let paths: Vec<&str> = input
+        .get("paths")
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|p| p.as_str()).collect())
+        .unwrap_or_default();
+
+    // resolve_ref falls back to HEAD, which suits an untagged release but
+    // would hide a typo in a ref the model asked for.
+    for key in ["from", "to"] {
+        if let Some(r) = input.get(key).and_then(|v| v.as_str())
+            && !git::ref_exists(ctx.repo_root, r)
+        {
+            return Err(Error::Tool(format!("diff_range: unknown ref: {r}")));
+        }
+    }
+    let from_sha = git::resolve_ref(ctx.repo_root, from)?;
+    let to_sha = git::resolve_ref(ctx.repo_root, to)?;
+
+    let mut args = vec![
+        "diff".to_string(),
+        "-M".into(),
+        "--stat=200".into(),
+        "--summary".into(),
+    ];
+    if patch {
+        args.push("--patch".into());
+    }
+    args.push(format!("{from_sha}..{to_sha}"));
+    args.push("--".into());
+    args.extend(paths.iter().map(|p| super::pathspec(p)));
+    args.extend(super::deny_pathspecs(&ctx.config.read_deny));
+
+    let output = process::cmd("git", &args).cwd(ctx.repo_root).read()?;
+    if output.trim().is_empty() {
+        return Ok(format!("No changes between {from} and {to}."));
+    }
+
+    let header = if paths.is_empty() {
+        format!("Diff {from}..{to}")
+    } else {
+        format!("Diff {from}..{to} (paths: {})", paths.join(", "))
+    };
+    let page = super::page_params(input, DEFAULT_LIMIT);
+    Ok(format!(
+        "{header}\n\n{}",
+        super::paginate(&output, page, MAX_BYTES)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempRepo;
+    use serde_json::json;
+
+    fn repo_with_range() -> TempRepo {
+        let repo = TempRepo::new();
+        repo.write_file("src/old_name.rs", &"fn kept() {}\n".repeat(20));
+        repo.write_file("docs/guide.md", "# Guide");
+        repo.commit("first");
+        repo.tag("v1.0.0");
+
+        std::fs::rename(
+            repo.path().join("src/old_name.rs"),
+            repo.path().join("src/new_name.rs"),
+        )
+        .unwrap();
+        repo.write_file("src/feature.rs", "pub fn feature() {}");
+        repo.write_file("docs/guide.md", "# Guide\n\nNew section");
+        repo.commit("second");
+        repo.tag("v1.1.0");
+        repo
+    }
+
+    fn ctx(repo: &TempRepo) -> ToolContext<'_> {
+        ToolContext {
+            tag: Some("v1.1.0"),
+            prev_tag: Some("v1.0.0"),
+            ..ToolContext::new(repo.path(), None)
+        }
+    }
+
+    #[test]
+    fn test_diff_range_stat_defaults_to_release_range() {
+        let repo = repo_with_range();
+        let result = execute(&ctx(&repo), &json!({})).unwrap();
+        assert!(result.starts_with("Diff v1.0.0..v1.1.0"));
+        assert!(result.contains("src/feature.rs"));
+        assert!(result.contains("docs/guide.md"));
+        assert!(result.contains("rename src/{old_name.rs => new_name.rs}"));
+        assert!(!result.contains("pub fn feature"));
+    }
+
+    #[test]
+    fn test_diff_range_patch_with_glob() {
+        let repo = repo_with_range();
+        let result = execute(&ctx(&repo), &json!({"patch": true, "paths": ["*.md"]})).unwrap();
+        assert!(result.contains("+New section"));
+        assert!(!result.contains("feature.rs"));
+    }
+
+    #[test]
+    fn test_diff_range_skips_denied_paths() {
+        let repo = repo_with_range();
+        let config = crate::config::ToolsConfig {
+            read_deny: vec!["docs/".into()],
+            ..Default::default()
+        };
+        let ctx = ToolContext {
+            config: &config,
+            ..ctx(&repo)
+        };
+        let result = execute(&ctx, &json!({"patch": true})).unwrap();
+        assert!(result.contains("+pub fn feature() {}"));
+        assert!(!result.contains("guide.md"), "result: {result}");
+        assert!(!result.contains("New section"));
+    }
+
+    #[test]
+    fn test_diff_range_paging() {
+        let repo = repo_with_range();
+        let result = execute(&ctx(&repo), &json!({"patch": true, "limit": 3})).unwrap();
+        assert!(result.contains("[lines 1-3 of "), "result: {result}");
+        assert!(result.contains("offset=3"));
+    }
+
+    #[test]
+    fn test_diff_range_rejects_unknown_refs() {
+        let repo = repo_with_range();
+        for input in [json!({"from": "v1.0"}), json!({"to": "v1.1.O"})] {
+            let err = execute(&ctx(&repo), &input).unwrap_err();
+            assert!(err.to_string().contains("unknown ref"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_diff_range_requires_from_without_prev_tag() {
+        let repo = repo_with_range();
+        let err = execute(&ToolContext::new(repo.path(), None), &json!({})).unwrap_err();
+        assert!(err.to_string().contains("missing 'from'"));
+    }
+}
@@ -0,0 +1,81 @@
+use serde_json::json;
+
+use super::ToolContext;
+use crate::changelog;
+use crate::error::{Error, Result};
+use crate::llm::ToolDefinition;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "get_changelog_entry".into(),
+        description: "Read the section for a past version from the changelog being updated: the repository's CHANGELOG.md, or the package's own when it has one.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "version": {
+                    "type": "string",
+                    "description": "Version or tag to look up (e.g. '1.2.0' or 'v1.2.0')"
+                }
+            },
+            "required": ["version"]
+        }),
+    }
+}
+
+pub fn execute(ctx: &ToolContext<'_>, input: &serde_json::Value) -> Result<String> {
+    let version = input["version"]
+        .as_str()
+        .ok_or_else(|| Error::Tool("get_changelog_entry: missing 'version' parameter".into()))?;
+
+    let path = match ctx.changelog {
+        Some(path) => path.to_path_buf(),
+        None => ctx.repo_root.join("CHANGELOG.md"),
+    };
+    Ok(changelog::read_entry(&path, version).unwrap_or_else(|| {
+        let name = path.strip_prefix(ctx.repo_root).unwrap_or(&path);
+        format!("No {} section found for {version}.", name.display())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TempRepo;
+
+    #[test]
+    fn test_get_changelog_entry() {
+        let repo = TempRepo::new();
+        repo.write_file(
+            "CHANGELOG.md",
+            "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2024-02-01\n\n- New\n\n## [1.0.0] - 2024-01-01\n\n- Deprecated `--old`\n",
+        );
+
+        let ctx = ToolContext::new(repo.path(), None);
+        let result = execute(&ctx, &json!({"version": "v1.0.0"})).unwrap();
+        assert_eq!(result, "## [1.0.0] - 2024-01-01\n\n- Deprecated `--old`");
+
+        let result = execute(&ctx, &json!({"version": "0.9.0"})).unwrap();
+        assert_eq!(result, "No CHANGELOG.md section found for 0.9.0.");
+    }
+
+    #[test]
+    fn test_get_changelog_entry_reads_package_changelog() {
+        let repo = TempRepo::new();
+        repo.write_file("CHANGELOG.md", "## [1.0.0]\n\n- Root\n");
+        repo.write_file("packages/cli/CHANGELOG.md", "## [1.0.0]\n\n- CLI\n");
+
+        let changelog = repo.path().join("packages/cli/CHANGELOG.md");
+        let ctx = ToolContext {
+            changelog: Some(&changelog),
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({"version": "1.0.0"})).unwrap();
+        assert_eq!(result, "## [1.0.0]\n\n- CLI");
+
+        let result = execute(&ctx, &json!({"version": "2.0.0"})).unwrap();
+        assert_eq!(
+            result,
+            "No packages/cli/CHANGELOG.md section found for 2.0.0."
+        );
+    }
+}
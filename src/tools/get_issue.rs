@@ -1,13 +1,16 @@
 use serde_json::json;
 
 use crate::error::{Error, Result};
-use crate::github::GitHubClient;
+use crate::forge::{ForgeClient, ForgeKind};
 use crate::llm::ToolDefinition;
 
-pub fn definition() -> ToolDefinition {
+pub fn definition(kind: ForgeKind) -> ToolDefinition {
     ToolDefinition {
         name: "get_issue".into(),
-        description: "Fetch details of a GitHub issue (title, body, labels, state, author).".into(),
+        description: format!(
+            "Fetch details of a {} issue, referenced as #123 (title, body, labels, state, author).",
+            kind.name()
+        ),
         input_schema: json!({
             "type": "object",
             "properties": {
@@ -21,12 +24,12 @@ pub fn definition() -> ToolDefinition {
     }
 }
 
-pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+pub async fn execute(forge: &ForgeClient, input: &serde_json::Value) -> Result<String> {
     let number = input["number"]
         .as_u64()
         .ok_or_else(|| Error::Tool("get_issue: missing 'number' parameter".into()))?;
 
-    let issue = github.get_issue(number).await?;
+    let issue = forge.get_issue(number).await?;
     Ok(format!(
         "Issue #{}: {}\nState: {}\nAuthor: @{}\nLabels: {}\n\n{}",
         issue.number,
@@ -1,19 +1,32 @@
 use serde_json::json;
 
 use crate::error::{Error, Result};
-use crate::github::GitHubClient;
+use crate::forge::{ForgeClient, ForgeKind};
 use crate::llm::ToolDefinition;
 
-pub fn definition() -> ToolDefinition {
+pub fn definition(kind: ForgeKind) -> ToolDefinition {
+    let (description, number) = match kind {
+        ForgeKind::GitLab => (
+            "Fetch details of a GitLab merge request, referenced as !123 (title, description, labels, author).".to_string(),
+            "Merge request number: 123 for !123",
+        ),
+        _ => (
+            format!(
+                "Fetch details of a {} pull request (title, body, labels, author).",
+                kind.name()
+            ),
+            "PR number",
+        ),
+    };
     ToolDefinition {
         name: "get_pr".into(),
-        description: "Fetch details of a GitHub pull request (title, body, labels, author).".into(),
+        description,
         input_schema: json!({
             "type": "object",
             "properties": {
                 "number": {
                     "type": "integer",
-                    "description": "PR number"
+                    "description": number
                 }
             },
             "required": ["number"]
@@ -21,14 +34,18 @@ pub fn definition() -> ToolDefinition {
     }
 }
 
-pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+pub async fn execute(forge: &ForgeClient, input: &serde_json::Value) -> Result<String> {
     let number = input["number"]
         .as_u64()
         .ok_or_else(|| Error::Tool("get_pr: missing 'number' parameter".into()))?;
 
-    let pr = github.get_pr(number).await?;
+    let pr = forge.get_pr(number).await?;
+    let kind = match forge.kind() {
+        ForgeKind::GitLab => "MR !",
+        _ => "PR #",
+    };
     Ok(format!(
-        "PR #{}: {}\nAuthor: @{}\nLabels: {}\n\n{}",
+        "{kind}{}: {}\nAuthor: @{}\nLabels: {}\n\n{}",
         pr.number,
         pr.title,
         pr.user.login,
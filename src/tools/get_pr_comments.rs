@@ -0,0 +1,156 @@
+use serde_json::json;
+
+use crate::error::{Error, Result};
+use crate::github::{Comment, GitHubClient};
+use crate::llm::ToolDefinition;
+
+const PER_PAGE: u8 = 50;
+const MAX_COMMENT_CHARS: usize = 2000;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "get_pr_comments".into(),
+        description: "Fetch the discussion on a GitHub pull request: conversation comments and inline review comments. Useful for the reasoning behind a change and migration notes. Bot comments are skipped by default.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "number": {
+                    "type": "integer",
+                    "description": "PR number"
+                },
+                "page": {
+                    "type": "integer",
+                    "description": "Page of comments to fetch, starting at 1. Defaults to 1."
+                },
+                "include_bots": {
+                    "type": "boolean",
+                    "description": "Include comments from bots (CI, coverage, dependency bots). Defaults to false."
+                }
+            },
+            "required": ["number"]
+        }),
+    }
+}
+
+pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+    let number = input["number"]
+        .as_u64()
+        .ok_or_else(|| Error::Tool("get_pr_comments: missing 'number' parameter".into()))?;
+    let page = input
+        .get("page")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1)
+        .max(1) as u32;
+    let include_bots = input
+        .get("include_bots")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let (comments, reviews) = tokio::try_join!(
+        github.list_issue_comments(number, page, PER_PAGE),
+        github.list_review_comments(number, page, PER_PAGE),
+    )?;
+    let has_more = comments.len() == PER_PAGE as usize || reviews.len() == PER_PAGE as usize;
+
+    let keep = |c: &&Comment| include_bots || !c.user.is_bot();
+    let comments: Vec<String> = comments.iter().filter(keep).map(format_comment).collect();
+    let reviews: Vec<String> = reviews.iter().filter(keep).map(format_comment).collect();
+
+    let mut out = format!("Comments on PR #{number} (page {page})");
+    if comments.is_empty() && reviews.is_empty() {
+        out.push_str("\n\nNo comments.");
+    }
+    if !comments.is_empty() {
+        out.push_str("\n\n## Conversation\n\n");
+        out.push_str(&comments.join("\n\n"));
+    }
+    if !reviews.is_empty() {
+        out.push_str("\n\n## Review comments\n\n");
+        out.push_str(&reviews.join("\n\n"));
+    }
+    if has_more {
+        out.push_str(&format!(
+            "\n\n[more comments available; pass page={} to continue]",
+            page + 1
+        ));
+    }
+    Ok(out)
+}
+
+fn format_comment(comment: &Comment) -> String {
+    let date = comment.created_at.get(..10).unwrap_or(&comment.created_at);
+    let location = comment
+        .path
+        .as_deref()
+        .map(|p| format!(" on {p}"))
+        .unwrap_or_default();
+    let body = comment.body.as_deref().unwrap_or("").trim();
+    let body = match body.char_indices().nth(MAX_COMMENT_CHARS) {
+        Some((end, _)) => format!("{}... [trimmed]", &body[..end]),
+        None => body.to_string(),
+    };
+    format!("@{} ({date}){location}:\n{body}", comment.user.login)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn setup(comments: serde_json::Value, reviews: serde_json::Value) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/issues/42/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(comments))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(reviews))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_get_pr_comments_filters_bots() {
+        let server = setup(
+            json!([
+                {"user": {"login": "alice", "type": "User"}, "body": "Breaking: `--foo` is now `--bar`.", "created_at": "2024-01-01T10:00:00Z"},
+                {"user": {"login": "github-actions[bot]", "type": "Bot"}, "body": "CI passed", "created_at": "2024-01-01T11:00:00Z"}
+            ]),
+            json!([
+                {"user": {"login": "bob", "type": "User"}, "body": "Should this be documented?", "created_at": "2024-01-02T09:00:00Z", "path": "src/cli.rs"}
+            ]),
+        )
+        .await;
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", server.uri()).unwrap();
+
+        let result = execute(&gh, &json!({"number": 42})).await.unwrap();
+        assert!(result.contains("@alice (2024-01-01):\nBreaking: `--foo` is now `--bar`."));
+        assert!(result.contains("@bob (2024-01-02) on src/cli.rs:"));
+        assert!(!result.contains("CI passed"));
+        assert!(!result.contains("more comments"));
+
+        let result = execute(&gh, &json!({"number": 42, "include_bots": true}))
+            .await
+            .unwrap();
+        assert!(result.contains("@github-actions[bot]"));
+    }
+
+    #[tokio::test]
+    async fn test_get_pr_comments_trims_and_pages() {
+        let comments: Vec<_> = (0..PER_PAGE)
+            .map(|i| json!({"user": {"login": format!("user{i}")}, "body": "x".repeat(3000), "created_at": "2024-01-01T00:00:00Z"}))
+            .collect();
+        let server = setup(json!(comments), json!([])).await;
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", server.uri()).unwrap();
+
+        let result = execute(&gh, &json!({"number": 42})).await.unwrap();
+        assert!(result.contains(&format!("{}... [trimmed]", "x".repeat(2000))));
+        assert!(!result.contains(&"x".repeat(2001)));
+        assert!(result.ends_with("pass page=2 to continue]"));
+        assert!(!result.contains("## Review comments"));
+    }
+}
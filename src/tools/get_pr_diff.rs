@@ -1,19 +1,44 @@
 use serde_json::json;
 
 use crate::error::{Error, Result};
-use crate::github::GitHubClient;
+use crate::forge::{ForgeClient, ForgeKind};
 use crate::llm::ToolDefinition;
 
-pub fn definition() -> ToolDefinition {
+const DEFAULT_LIMIT: usize = 1000;
+const MAX_BYTES: usize = 50_000;
+
+pub fn definition(kind: ForgeKind) -> ToolDefinition {
+    let (description, number) = match kind {
+        ForgeKind::GitLab => (
+            "Fetch the diff of a GitLab merge request, referenced as !123.".to_string(),
+            "Merge request number: 123 for !123",
+        ),
+        _ => (
+            format!("Fetch the diff of a {} pull request.", kind.name()),
+            "PR number",
+        ),
+    };
     ToolDefinition {
         name: "get_pr_diff".into(),
-        description: "Fetch the diff of a GitHub pull request.".into(),
+        description,
         input_schema: json!({
             "type": "object",
             "properties": {
                 "number": {
                     "type": "integer",
-                    "description": "PR number"
+                    "description": number
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Line to start from (0-based), for paging through large diffs. Defaults to 0."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return. Defaults to 1000."
+                },
+                "byte_offset": {
+                    "type": "integer",
+                    "description": "Byte to resume from within the line at offset, when a single line is too long for one page. Defaults to 0."
                 }
             },
             "required": ["number"]
@@ -21,10 +46,76 @@ pub fn definition() -> ToolDefinition {
     }
 }
 
-pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+pub async fn execute(
+    forge: &ForgeClient,
+    read_deny: &[String],
+    input: &serde_json::Value,
+) -> Result<String> {
     let number = input["number"]
         .as_u64()
         .ok_or_else(|| Error::Tool("get_pr_diff: missing 'number' parameter".into()))?;
 
-    github.get_pr_diff(number).await
+    let diff = super::strip_denied_diff(&forge.get_pr_diff(number).await?, read_deny);
+    let page = super::page_params(input, DEFAULT_LIMIT);
+    Ok(super::paginate(&diff, page, MAX_BYTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_pr_diff_paging() {
+        let server = MockServer::start().await;
+        let diff: String = (1..=1500).map(|i| format!("+line {i}\n")).collect();
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(&diff))
+            .mount(&server)
+            .await;
+        let gh = ForgeClient::GitHub(
+            crate::github::GitHubClient::with_base_url("token".into(), "owner/repo", server.uri())
+                .unwrap(),
+        );
+
+        let first = execute(&gh, &[], &json!({"number": 42})).await.unwrap();
+        assert!(first.starts_with("[lines 1-1000 of 1500"));
+        assert!(first.contains("pass offset=1000 to continue"));
+
+        let rest = execute(&gh, &[], &json!({"number": 42, "offset": 1000}))
+            .await
+            .unwrap();
+        assert!(rest.starts_with("[lines 1001-1500 of 1500"));
+        assert!(rest.ends_with("+line 1500"));
+    }
+
+    #[tokio::test]
+    async fn test_get_pr_diff_skips_denied_files() {
+        let server = MockServer::start().await;
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n+pub fn f() {}\n\
+            diff --git a/secrets/key.txt b/secrets/key.txt\nnew file mode 100644\n--- /dev/null\n+++ b/secrets/key.txt\n+hunter2\n\
+            diff --git a/old.env b/config/app.env\nsimilarity index 90%\nrename from old.env\nrename to config/app.env\n+TOKEN=abc\n\
+            diff --git a/README.md b/README.md\n+Docs\n";
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(diff))
+            .mount(&server)
+            .await;
+        let gh = ForgeClient::GitHub(
+            crate::github::GitHubClient::with_base_url("token".into(), "owner/repo", server.uri())
+                .unwrap(),
+        );
+
+        let read_deny = vec!["secrets/**".into(), "*.env".into()];
+        let result = execute(&gh, &read_deny, &json!({"number": 42}))
+            .await
+            .unwrap();
+        assert!(result.contains("+pub fn f() {}"));
+        assert!(result.contains("+Docs"));
+        assert!(!result.contains("secrets/key.txt"));
+        assert!(!result.contains("hunter2"));
+        assert!(!result.contains("TOKEN=abc"));
+    }
 }
@@ -0,0 +1,80 @@
+use serde_json::json;
+
+use crate::error::{Error, Result};
+use crate::github::GitHubClient;
+use crate::llm::ToolDefinition;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "get_pr_files".into(),
+        description: "List the files a GitHub pull request changed, with status and line counts. Cheaper than get_pr_diff for telling user-facing changes from internal ones.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "number": {
+                    "type": "integer",
+                    "description": "PR number"
+                }
+            },
+            "required": ["number"]
+        }),
+    }
+}
+
+pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+    let number = input["number"]
+        .as_u64()
+        .ok_or_else(|| Error::Tool("get_pr_files: missing 'number' parameter".into()))?;
+
+    let files = github.list_pr_files(number).await?;
+    if files.is_empty() {
+        return Ok(format!("PR #{number} changed no files."));
+    }
+
+    let additions: u64 = files.iter().map(|f| f.additions).sum();
+    let deletions: u64 = files.iter().map(|f| f.deletions).sum();
+    let mut out = format!(
+        "PR #{number} changed {} files (+{additions} -{deletions})\n",
+        files.len()
+    );
+    for f in &files {
+        let path = match &f.previous_filename {
+            Some(prev) => format!("{prev} => {}", f.filename),
+            None => f.filename.clone(),
+        };
+        out.push_str(&format!(
+            "\n{:<9} {path} (+{} -{})",
+            f.status, f.additions, f.deletions
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_pr_files() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/pulls/42/files"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"filename": "src/cli.rs", "status": "modified", "additions": 12, "deletions": 3},
+                {"filename": "docs/usage.md", "status": "renamed", "additions": 1, "deletions": 1, "previous_filename": "docs/cli.md"}
+            ])))
+            .mount(&server)
+            .await;
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", server.uri()).unwrap();
+
+        let result = execute(&gh, &json!({"number": 42})).await.unwrap();
+        assert_eq!(
+            result,
+            "PR #42 changed 2 files (+13 -4)\n\
+             \nmodified  src/cli.rs (+12 -3)\
+             \nrenamed   docs/cli.md => docs/usage.md (+1 -1)"
+        );
+    }
+}
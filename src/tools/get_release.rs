@@ -0,0 +1,97 @@
+use serde_json::json;
+
+use crate::error::{Error, Result};
+use crate::github::{GitHubClient, Release};
+use crate::llm::ToolDefinition;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "get_release".into(),
+        description: "Fetch a past GitHub release by tag (title, date, and full notes). Useful for checking what was said when a feature was introduced or deprecated.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "tag": {
+                    "type": "string",
+                    "description": "Release tag (e.g. 'v1.2.0')"
+                }
+            },
+            "required": ["tag"]
+        }),
+    }
+}
+
+pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+    let tag = input["tag"]
+        .as_str()
+        .ok_or_else(|| Error::Tool("get_release: missing 'tag' parameter".into()))?;
+
+    match github.get_release_by_tag(tag).await? {
+        Some(release) => Ok(format!(
+            "{}\n\n{}",
+            summary_line(&release),
+            release
+                .body
+                .as_deref()
+                .filter(|b| !b.trim().is_empty())
+                .unwrap_or("(no release notes)")
+        )),
+        None => Ok(format!("No GitHub release found for tag {tag}.")),
+    }
+}
+
+/// One-line description of a release: tag, title, date and flags.
+pub fn summary_line(release: &Release) -> String {
+    let mut line = release.tag_name.clone();
+    if let Some(name) = release.name.as_deref().filter(|n| *n != release.tag_name) {
+        line.push_str(&format!(": {name}"));
+    }
+    if let Some(date) = &release.published_at {
+        line.push_str(&format!(" ({})", date.get(..10).unwrap_or(date)));
+    }
+    if release.draft {
+        line.push_str(" [draft]");
+    }
+    if release.prerelease {
+        line.push_str(" [prerelease]");
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_release() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases/tags/v1.2.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1,
+                "tag_name": "v1.2.0",
+                "name": "Plugins",
+                "body": "Deprecated `--legacy`.",
+                "published_at": "2024-05-01T00:00:00Z"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", server.uri()).unwrap();
+
+        let result = execute(&gh, &json!({"tag": "v1.2.0"})).await.unwrap();
+        assert_eq!(
+            result,
+            "v1.2.0: Plugins (2024-05-01)\n\nDeprecated `--legacy`."
+        );
+
+        let result = execute(&gh, &json!({"tag": "v0.1.0"})).await.unwrap();
+        assert_eq!(result, "No GitHub release found for tag v0.1.0.");
+    }
+}
@@ -1,11 +1,15 @@
-use std::path::Path;
-
 use serde_json::json;
 use xx::process;
 
+use super::ToolContext;
+use super::read_file::is_denied;
 use crate::error::{Error, Result};
+use crate::git;
 use crate::llm::ToolDefinition;
 
+const DEFAULT_LIMIT: usize = 1000;
+const MAX_BYTES: usize = 50_000;
+
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
         name: "git_show".into(),
@@ -16,6 +20,18 @@ pub fn definition() -> ToolDefinition {
                 "ref": {
                     "type": "string",
                     "description": "Commit SHA, tag, branch, or other git ref"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Line to start from (0-based), for paging through large commits. Defaults to 0."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return. Defaults to 1000."
+                },
+                "byte_offset": {
+                    "type": "integer",
+                    "description": "Byte to resume from within the line at offset, when a single line is too long for one page. Defaults to 0."
                 }
             },
             "required": ["ref"]
@@ -23,24 +39,38 @@ pub fn definition() -> ToolDefinition {
     }
 }
 
-pub fn execute(repo_root: &Path, input: &serde_json::Value) -> Result<String> {
+pub fn execute(ctx: &ToolContext<'_>, input: &serde_json::Value) -> Result<String> {
     let git_ref = input["ref"]
         .as_str()
         .ok_or_else(|| Error::Tool("git_show: missing 'ref' parameter".into()))?;
 
-    let output = process::cmd("git", ["show", "--stat", "--patch", git_ref])
-        .cwd(repo_root)
-        .read()?;
-
-    // Truncate very large output to avoid blowing up context
-    if output.len() > 50_000 {
-        Ok(format!(
-            "{}...\n\n[output truncated at 50KB]",
-            &output[..50_000]
-        ))
-    } else {
-        Ok(output)
+    // Pathspecs don't filter blobs or trees, so `rev:path` refs are checked
+    // against read_deny directly and anything else must name a commit.
+    match git_ref.split_once(':') {
+        Some((_, path)) if is_denied(path, &ctx.config.read_deny) => {
+            return Err(Error::Tool(format!(
+                "git_show: {path} is denied by read_deny"
+            )));
+        }
+        Some(_) => {}
+        None if !git::ref_exists(ctx.repo_root, git_ref) => {
+            return Err(Error::Tool(format!("git_show: unknown commit: {git_ref}")));
+        }
+        None => {}
     }
+
+    let mut args = vec![
+        "show".to_string(),
+        "--stat".into(),
+        "--patch".into(),
+        git_ref.into(),
+        "--".into(),
+    ];
+    args.extend(super::deny_pathspecs(&ctx.config.read_deny));
+    let output = process::cmd("git", &args).cwd(ctx.repo_root).read()?;
+
+    let page = super::page_params(input, DEFAULT_LIMIT);
+    Ok(super::paginate(&output, page, MAX_BYTES))
 }
 
 #[cfg(test)]
@@ -55,18 +85,101 @@ mod tests {
         repo.write_file("hello.txt", "hello world");
         repo.commit("Add hello file");
 
-        let result = execute(repo.path(), &json!({"ref": "HEAD"})).unwrap();
+        let result = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"ref": "HEAD"}),
+        )
+        .unwrap();
         assert!(result.contains("Add hello file"));
         assert!(result.contains("hello.txt"));
     }
 
+    #[test]
+    fn test_git_show_paging() {
+        let repo = TempRepo::new();
+        let contents: String = (1..=2000).map(|i| format!("line {i}\n")).collect();
+        repo.write_file("long.txt", &contents);
+        repo.commit("Add long file");
+
+        let first = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"ref": "HEAD"}),
+        )
+        .unwrap();
+        assert!(first.contains("pass offset=1000 to continue"));
+        assert!(first.contains("Add long file"));
+
+        let rest = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"ref": "HEAD", "offset": 1000, "limit": 2000}),
+        )
+        .unwrap();
+        assert!(rest.starts_with("[lines 1001-"));
+        assert!(rest.contains("+line 2000"));
+        assert!(!rest.contains("Add long file"));
+    }
+
+    #[test]
+    fn test_git_show_skips_denied_files() {
+        let repo = TempRepo::new();
+        repo.write_file("src/lib.rs", "pub fn f() {}");
+        repo.write_file("secrets/key.txt", "hunter2");
+        repo.commit("Add files");
+
+        let config = crate::config::ToolsConfig {
+            read_deny: vec!["secrets/**".into()],
+            ..Default::default()
+        };
+        let ctx = ToolContext {
+            config: &config,
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({"ref": "HEAD"})).unwrap();
+        assert!(result.contains("+pub fn f() {}"));
+        assert!(!result.contains("hunter2"));
+        assert!(!result.contains("secrets/key.txt"));
+    }
+
+    #[test]
+    fn test_git_show_refuses_denied_blob() {
+        let repo = TempRepo::new();
+        repo.write_file("src/lib.rs", "pub fn f() {}");
+        repo.write_file("secrets/key.txt", "hunter2");
+        repo.commit("Add files");
+
+        let config = crate::config::ToolsConfig {
+            read_deny: vec!["secrets/**".into()],
+            ..Default::default()
+        };
+        let ctx = ToolContext {
+            config: &config,
+            ..ToolContext::new(repo.path(), None)
+        };
+        let err = execute(&ctx, &json!({"ref": "HEAD:secrets/key.txt"})).unwrap_err();
+        assert!(err.to_string().contains("denied by read_deny"));
+
+        let blob = process::cmd("git", ["rev-parse", "HEAD:secrets/key.txt"])
+            .cwd(repo.path())
+            .read()
+            .unwrap();
+        let err = execute(&ctx, &json!({"ref": blob})).unwrap_err();
+        assert!(err.to_string().contains("unknown commit"));
+
+        let result = execute(&ctx, &json!({"ref": "HEAD:src/lib.rs"})).unwrap();
+        assert!(result.contains("pub fn f() {}"));
+    }
+
     #[test]
     fn test_git_show_missing_ref() {
         let repo = TempRepo::new();
         repo.write_file("hello.txt", "hello");
         repo.commit("init");
 
-        let err = execute(repo.path(), &json!({"ref": "nonexistent_ref_abc123"})).unwrap_err();
+        let err = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"ref": "nonexistent_ref_abc123"}),
+        )
+        .unwrap_err();
         assert!(err.to_string().contains("nonexistent_ref_abc123"));
     }
 }
@@ -3,23 +3,52 @@ use std::process::Command;
 
 use serde_json::json;
 
+use super::ToolContext;
 use crate::error::{Error, Result};
+use crate::git;
 use crate::llm::ToolDefinition;
 
+const DEFAULT_LIMIT: usize = 1000;
+const MAX_BYTES: usize = 50_000;
+
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
         name: "grep".into(),
-        description: "Search file contents using ripgrep (rg). Returns matching lines with file paths and line numbers.".into(),
+        description: "Search tracked file contents as of the release being documented (or another ref) using git grep. Returns matching lines with file paths and line numbers.".into(),
         input_schema: json!({
             "type": "object",
             "properties": {
                 "pattern": {
                     "type": "string",
-                    "description": "Regex pattern to search for"
+                    "description": "POSIX extended regex to search for, as in `git grep -E`. Perl classes like \\d and lookarounds aren't supported; use [0-9] and [[:space:]] instead."
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Git ref (tag, branch, or SHA) to search. Defaults to the release tag."
                 },
                 "glob": {
                     "type": "string",
-                    "description": "Optional file glob to restrict search (e.g. '*.rs')"
+                    "description": "Optional file glob to restrict search (e.g. '*.rs', 'src/**/*.ts')"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Match case-insensitively. Defaults to false."
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "description": "Lines of context to show around each match. Defaults to 0."
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Line of output to start from, for paging through many matches. Defaults to 0."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return. Defaults to 1000."
+                },
+                "byte_offset": {
+                    "type": "integer",
+                    "description": "Byte to resume from within the line at offset, when a single line is too long for one page. Defaults to 0."
                 }
             },
             "required": ["pattern"]
@@ -27,29 +56,153 @@ pub fn definition() -> ToolDefinition {
     }
 }
 
-pub fn execute(repo_root: &Path, input: &serde_json::Value) -> Result<String> {
+struct GrepArgs<'a> {
+    pattern: &'a str,
+    glob: Option<&'a str>,
+    case_insensitive: bool,
+    context_lines: u64,
+    max_count: usize,
+    read_deny: &'a [String],
+}
+
+pub fn execute(ctx: &ToolContext<'_>, input: &serde_json::Value) -> Result<String> {
     let pattern = input["pattern"]
         .as_str()
         .ok_or_else(|| Error::Tool("grep: missing 'pattern' parameter".into()))?;
+    let args = GrepArgs {
+        pattern,
+        glob: input.get("glob").and_then(|v| v.as_str()),
+        case_insensitive: input
+            .get("case_insensitive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        context_lines: input
+            .get("context_lines")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            .min(20),
+        max_count: ctx.config.grep_max_matches(),
+        read_deny: &ctx.config.read_deny,
+    };
 
+    // Search the release by default, like read_file. An untagged target
+    // (HEAD, or a version not tagged yet) searches the working tree.
+    let git_ref = match input.get("ref").and_then(|v| v.as_str()) {
+        Some(r) => Some(r),
+        None => ctx
+            .tag
+            .filter(|t| *t != "HEAD" && git::ref_exists(ctx.repo_root, t)),
+    };
+
+    let result = match git_ref {
+        Some(git_ref) => git_grep(ctx.repo_root, &args, Some(git_ref))?,
+        None if ctx.config.use_ripgrep => match ripgrep(ctx.repo_root, &args) {
+            Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                git_grep(ctx.repo_root, &args, None)?
+            }
+            other => other?,
+        },
+        None => git_grep(ctx.repo_root, &args, None)?,
+    };
+    let result = super::strip_denied_lines(&result, args.read_deny);
+
+    if result.is_empty() {
+        Ok("No matches found.".into())
+    } else {
+        let page = super::page_params(input, DEFAULT_LIMIT);
+        Ok(super::paginate(&result, page, MAX_BYTES))
+    }
+}
+
+/// `git grep` only ever sees tracked files, which keeps grep inside the same
+/// sandbox as read_file.
+fn git_grep(repo_root: &Path, args: &GrepArgs, git_ref: Option<&str>) -> Result<String> {
+    if let Some(git_ref) = git_ref
+        && git_ref.starts_with('-')
+    {
+        return Err(Error::Tool(format!("grep: invalid ref: {git_ref}")));
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.args([
+        "grep",
+        "--line-number",
+        "--no-color",
+        "-I",
+        "--extended-regexp",
+        "--max-count",
+        &args.max_count.to_string(),
+    ])
+    .current_dir(repo_root);
+    if args.case_insensitive {
+        cmd.arg("--ignore-case");
+    }
+    if args.context_lines > 0 {
+        cmd.args(["--context", &args.context_lines.to_string()]);
+    }
+    cmd.args(["-e", args.pattern]);
+    if let Some(git_ref) = git_ref {
+        cmd.arg(git_ref);
+    }
+    cmd.arg("--");
+    if let Some(glob) = args.glob {
+        cmd.arg(super::pathspec(glob));
+    }
+    cmd.args(super::deny_pathspecs(args.read_deny));
+
+    let output = cmd
+        .output()
+        .map_err(|e| Error::Tool(format!("grep: {e}")))?;
+
+    // git grep exits 1 for no matches; anything else non-zero is an error
+    if !output.status.success() && output.status.code() != Some(1) {
+        return Err(Error::Tool(format!(
+            "grep: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(git_ref) = git_ref else {
+        return Ok(stdout.into_owned());
+    };
+    // Drop the `<ref>:` prefix git adds to every line when searching a tree.
+    let prefix = format!("{git_ref}:");
+    Ok(stdout
+        .lines()
+        .map(|line| line.strip_prefix(&prefix).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Working-tree search with ripgrep, for repos that opt in with
+/// `tools.use_ripgrep`. Unlike git grep it also sees untracked files that
+/// aren't gitignored.
+fn ripgrep(repo_root: &Path, args: &GrepArgs) -> Result<String> {
     let mut cmd = Command::new("rg");
     cmd.args([
         "--no-config",
         "--line-number",
         "--no-heading",
         "--max-count",
-        "50",
+        &args.max_count.to_string(),
     ])
-    .arg(pattern)
     .current_dir(repo_root);
-
-    if let Some(glob) = input.get("glob").and_then(|v| v.as_str()) {
+    if args.case_insensitive {
+        cmd.arg("--ignore-case");
+    }
+    if args.context_lines > 0 {
+        cmd.args(["--context", &args.context_lines.to_string()]);
+    }
+    if let Some(glob) = args.glob {
         cmd.args(["--glob", glob]);
     }
+    for pattern in args.read_deny {
+        cmd.args(["--glob", &format!("!{pattern}")]);
+    }
+    cmd.args(["-e", args.pattern]);
 
-    let output = cmd
-        .output()
-        .map_err(|e| Error::Tool(format!("grep: {e}")))?;
+    let output = cmd.output()?;
 
     // Exit code 2+ is an actual error; rg returns 1 for no matches
     if output.status.code().is_some_and(|c| c >= 2) {
@@ -59,27 +212,27 @@ pub fn execute(repo_root: &Path, input: &serde_json::Value) -> Result<String> {
         )));
     }
 
-    let result = String::from_utf8_lossy(&output.stdout);
-    if result.is_empty() {
-        Ok("No matches found.".into())
-    } else {
-        Ok(result.to_string())
-    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ToolsConfig;
     use crate::test_helpers::TempRepo;
     use serde_json::json;
 
+    fn execute_in(repo: &TempRepo, input: serde_json::Value) -> Result<String> {
+        execute(&ToolContext::new(repo.path(), None), &input)
+    }
+
     #[test]
     fn test_grep_basic() {
         let repo = TempRepo::new();
         repo.write_file("hello.txt", "hello world\nfoo bar");
         repo.commit("init");
 
-        let result = execute(repo.path(), &json!({"pattern": "hello"})).unwrap();
+        let result = execute_in(&repo, json!({"pattern": "hello"})).unwrap();
         assert!(
             result.contains("hello world"),
             "expected 'hello world' in: {result:?}"
@@ -92,7 +245,7 @@ mod tests {
         repo.write_file("hello.txt", "hello world");
         repo.commit("init");
 
-        let result = execute(repo.path(), &json!({"pattern": "zzzzz"})).unwrap();
+        let result = execute_in(&repo, json!({"pattern": "zzzzz"})).unwrap();
         assert_eq!(result, "No matches found.");
     }
 
@@ -103,7 +256,7 @@ mod tests {
         repo.write_file("b.txt", "fn main() {}");
         repo.commit("init");
 
-        let result = execute(repo.path(), &json!({"pattern": "fn main", "glob": "*.rs"})).unwrap();
+        let result = execute_in(&repo, json!({"pattern": "fn main", "glob": "*.rs"})).unwrap();
         assert!(result.contains("a.rs"), "expected 'a.rs' in: {result:?}");
         assert!(
             !result.contains("b.txt"),
@@ -114,7 +267,132 @@ mod tests {
     #[test]
     fn test_grep_missing_pattern() {
         let repo = TempRepo::new();
-        let err = execute(repo.path(), &json!({})).unwrap_err();
+        let err = execute_in(&repo, json!({})).unwrap_err();
         assert!(err.to_string().contains("missing 'pattern'"));
     }
+
+    #[test]
+    fn test_grep_skips_untracked_files() {
+        let repo = TempRepo::new();
+        repo.write_file("tracked.txt", "needle");
+        repo.commit("init");
+        repo.write_file(".env", "needle=secret");
+
+        let result = execute_in(&repo, json!({"pattern": "needle"})).unwrap();
+        assert!(result.contains("tracked.txt"));
+        assert!(!result.contains(".env"), "unexpected '.env' in: {result:?}");
+    }
+
+    #[test]
+    fn test_grep_skips_denied_files() {
+        let repo = TempRepo::new();
+        repo.write_file("src/lib.rs", "needle");
+        repo.write_file("secrets/key.txt", "needle=hunter2");
+        repo.write_file("certs/nested/server.pem", "needle");
+        repo.commit("init");
+        repo.tag("v1.0.0");
+
+        let config = ToolsConfig {
+            read_deny: vec!["secrets/**".into(), "*.pem".into()],
+            ..ToolsConfig::default()
+        };
+        for tag in [None, Some("v1.0.0")] {
+            let ctx = ToolContext {
+                config: &config,
+                tag,
+                ..ToolContext::new(repo.path(), None)
+            };
+            let result = execute(&ctx, &json!({"pattern": "needle"})).unwrap();
+            assert_eq!(result, "src/lib.rs:1:needle");
+        }
+    }
+
+    #[test]
+    fn test_grep_defaults_to_release_tag() {
+        let repo = TempRepo::new();
+        repo.write_file("src/lib.rs", "fn old_name() {}");
+        repo.commit("first");
+        repo.tag("v1.0.0");
+        repo.write_file("src/lib.rs", "fn new_name() {}");
+        repo.commit("second");
+
+        let ctx = ToolContext {
+            tag: Some("v1.0.0"),
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({"pattern": "_name"})).unwrap();
+        assert_eq!(result, "src/lib.rs:1:fn old_name() {}");
+
+        let result = execute(&ctx, &json!({"pattern": "_name", "ref": "HEAD"})).unwrap();
+        assert_eq!(result, "src/lib.rs:1:fn new_name() {}");
+    }
+
+    #[test]
+    fn test_grep_case_insensitive_with_context() {
+        let repo = TempRepo::new();
+        repo.write_file("notes.txt", "before\nNEEDLE\nafter\nunrelated");
+        repo.commit("init");
+
+        let result = execute_in(&repo, json!({"pattern": "needle"})).unwrap();
+        assert_eq!(result, "No matches found.");
+
+        let result = execute_in(
+            &repo,
+            json!({"pattern": "needle", "case_insensitive": true, "context_lines": 1}),
+        )
+        .unwrap();
+        assert!(result.contains("notes.txt-1-before"), "got: {result:?}");
+        assert!(result.contains("notes.txt:2:NEEDLE"));
+        assert!(result.contains("notes.txt-3-after"));
+        assert!(!result.contains("unrelated"));
+    }
+
+    #[test]
+    fn test_grep_schema_documents_paging_and_syntax() {
+        let def = definition();
+        let props = &def.input_schema["properties"];
+        for key in ["offset", "limit", "byte_offset"] {
+            assert_eq!(props[key]["type"], "integer", "{key}");
+        }
+        assert!(
+            props["pattern"]["description"]
+                .as_str()
+                .unwrap()
+                .contains("POSIX extended")
+        );
+    }
+
+    #[test]
+    fn test_grep_paging() {
+        let repo = TempRepo::new();
+        let contents: String = (1..=30).map(|i| format!("match {i}\n")).collect();
+        repo.write_file("many.txt", &contents);
+        repo.commit("init");
+
+        let result = execute_in(
+            &repo,
+            json!({"pattern": "match", "offset": 25, "limit": 10}),
+        )
+        .unwrap();
+        assert!(result.starts_with("[lines 26-30 of 30"), "{result}");
+        assert!(result.ends_with("many.txt:30:match 30"));
+    }
+
+    #[test]
+    fn test_grep_max_matches_is_configurable() {
+        let repo = TempRepo::new();
+        repo.write_file("many.txt", &"match\n".repeat(10));
+        repo.commit("init");
+
+        let config = ToolsConfig {
+            grep_max_matches: Some(3),
+            ..ToolsConfig::default()
+        };
+        let ctx = ToolContext {
+            config: &config,
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({"pattern": "match"})).unwrap();
+        assert_eq!(result.lines().count(), 3);
+    }
 }
@@ -0,0 +1,77 @@
+use serde_json::json;
+
+use crate::error::Result;
+use crate::github::GitHubClient;
+use crate::llm::ToolDefinition;
+
+const PER_PAGE: u8 = 30;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "list_releases".into(),
+        description: "List past GitHub releases, newest first (tag, title, date). Use get_release to read one.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "page": {
+                    "type": "integer",
+                    "description": "Page of releases, starting at 1. Defaults to 1."
+                }
+            }
+        }),
+    }
+}
+
+pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+    let page = input
+        .get("page")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1)
+        .max(1) as u32;
+
+    let releases = github.list_releases(page, PER_PAGE).await?;
+    if releases.is_empty() {
+        return Ok(format!("No releases on page {page}."));
+    }
+
+    let mut out = releases
+        .iter()
+        .map(super::get_release::summary_line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if releases.len() == PER_PAGE as usize {
+        out.push_str(&format!(
+            "\n\n[more releases available; pass page={} to continue]",
+            page + 1
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_list_releases() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": 3, "tag_name": "v2.0.0-rc.1", "name": null, "body": "", "prerelease": true, "published_at": "2024-06-01T00:00:00Z"},
+                {"id": 2, "tag_name": "v1.9.0", "name": "Faster", "body": "", "draft": true, "published_at": null}
+            ])))
+            .mount(&server)
+            .await;
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", server.uri()).unwrap();
+
+        let result = execute(&gh, &json!({"page": 2})).await.unwrap();
+        assert_eq!(
+            result,
+            "v2.0.0-rc.1 (2024-06-01) [prerelease]\nv1.9.0: Faster [draft]"
+        );
+    }
+}
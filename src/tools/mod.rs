@@ -1,20 +1,77 @@
+pub mod custom;
+pub mod dependency_changes;
+pub mod diff_range;
+pub mod get_changelog_entry;
 pub mod get_commits;
 pub mod get_issue;
 pub mod get_pr;
+pub mod get_pr_comments;
 pub mod get_pr_diff;
+pub mod get_pr_files;
+pub mod get_release;
 pub mod git_show;
 pub mod grep;
 pub mod list_files;
+pub mod list_releases;
 pub mod read_file;
+pub mod search_github;
 pub mod submit_release_notes;
 
 use std::collections::HashMap;
 use std::path::Path;
 
+use crate::config::{DEFAULT_TOOLS_CONFIG, ToolsConfig};
 use crate::error::Result;
-use crate::github::GitHubClient;
+use crate::forge::{ForgeClient, ForgeKind};
 use crate::llm::ToolDefinition;
 
+/// Names reserved by the built-in tools; custom tools may not reuse them.
+pub const BUILTIN_TOOLS: &[&str] = &[
+    "read_file",
+    "list_files",
+    "grep",
+    "git_show",
+    "get_commits",
+    "diff_range",
+    "dependency_changes",
+    "get_changelog_entry",
+    "submit_release_notes",
+    "get_pr",
+    "get_pr_comments",
+    "get_pr_diff",
+    "get_pr_files",
+    "get_issue",
+    "search_github",
+    "get_release",
+    "list_releases",
+];
+
+/// Everything a tool needs besides its own input.
+pub struct ToolContext<'a> {
+    pub repo_root: &'a Path,
+    pub forge: Option<&'a ForgeClient>,
+    pub config: &'a ToolsConfig,
+    /// The release being documented; tools that read the tree default to it.
+    pub tag: Option<&'a str>,
+    pub prev_tag: Option<&'a str>,
+    /// The changelog `get_changelog_entry` reads, from
+    /// `[packages.<name>].changelog`; CHANGELOG.md at the root when unset.
+    pub changelog: Option<&'a Path>,
+}
+
+impl<'a> ToolContext<'a> {
+    pub fn new(repo_root: &'a Path, forge: Option<&'a ForgeClient>) -> Self {
+        Self {
+            repo_root,
+            forge,
+            config: &DEFAULT_TOOLS_CONFIG,
+            tag: None,
+            prev_tag: None,
+            changelog: None,
+        }
+    }
+}
+
 /// In-memory cache for tool call results, keyed by (tool_name, input_json).
 /// Only successful results are cached. Avoids redundant file reads, git
 /// operations, and GitHub API calls when the LLM calls the same tool with
@@ -44,10 +101,13 @@ impl ToolCache {
     }
 }
 
+/// Tool definitions for the model. `forge` is the kind of forge client
+/// available, if any; GitLab and Gitea get only the tools their APIs back.
 pub fn all_definitions(
-    has_github: bool,
+    forge: Option<ForgeKind>,
     include_release_notes: bool,
     include_changelog: bool,
+    config: &ToolsConfig,
 ) -> Vec<ToolDefinition> {
     let mut defs = vec![
         read_file::definition(),
@@ -55,47 +115,293 @@ pub fn all_definitions(
         grep::definition(),
         git_show::definition(),
         get_commits::definition(),
+        diff_range::definition(),
+        dependency_changes::definition(),
+        get_changelog_entry::definition(),
         submit_release_notes::definition(include_release_notes, include_changelog),
     ];
-    if has_github {
-        defs.push(get_pr::definition());
-        defs.push(get_pr_diff::definition());
-        defs.push(get_issue::definition());
+    if let Some(kind) = forge {
+        defs.push(get_pr::definition(kind));
+        defs.push(get_pr_diff::definition(kind));
+        defs.push(get_issue::definition(kind));
+    }
+    if forge == Some(ForgeKind::GitHub) {
+        defs.push(get_pr_comments::definition());
+        defs.push(get_pr_files::definition());
+        defs.push(search_github::definition());
+        defs.push(get_release::definition());
+        defs.push(list_releases::definition());
     }
+    defs.extend(config.custom.iter().map(custom::definition));
+    defs.retain(|d| config.is_enabled(&d.name));
     defs
 }
 
+/// Turn a path or glob from a tool input into a git pathspec.
+///
+/// Globs get git's `:(glob)` magic so `**` spans directories, and one
+/// without a `/` matches at any depth. Plain paths stay literal so they still
+/// match everything below a directory.
+pub fn pathspec(path: &str) -> String {
+    if !path.contains(['*', '?', '[']) {
+        path.to_string()
+    } else if path.contains('/') {
+        format!(":(glob){path}")
+    } else {
+        format!(":(glob)**/{path}")
+    }
+}
+
+/// Exclude pathspecs that keep git from reading files matched by
+/// `tools.read_deny`.
+///
+/// Each glob gets the same meaning as in [`read_file::is_denied`]: one without
+/// a `/` matches at any depth, and a match on a directory covers everything
+/// below it.
+pub fn deny_pathspecs(read_deny: &[String]) -> Vec<String> {
+    read_deny
+        .iter()
+        .flat_map(|pattern| {
+            let pattern = pattern.trim_start_matches("./");
+            let (pattern, dir_only) = match pattern.strip_suffix('/') {
+                Some(p) => (p, true),
+                None => (pattern, false),
+            };
+            let pattern = if pattern.contains('/') {
+                pattern.trim_start_matches('/').to_string()
+            } else {
+                format!("**/{pattern}")
+            };
+            let below = format!(":(exclude,glob){pattern}/**");
+            if dir_only {
+                vec![below]
+            } else {
+                vec![format!(":(exclude,glob){pattern}"), below]
+            }
+        })
+        .collect()
+}
+
+/// Drop output lines that start with a `path:` denied by `tools.read_deny`,
+/// like grep matches from a custom tool.
+pub fn strip_denied_lines(output: &str, read_deny: &[String]) -> String {
+    if read_deny.is_empty() {
+        return output.to_string();
+    }
+    output
+        .lines()
+        .filter(|line| {
+            !line
+                .split_once(':')
+                .is_some_and(|(path, _)| read_file::is_denied(path, read_deny))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop the sections of a unified diff whose old or new path is denied by
+/// `tools.read_deny`, for diffs that don't come from the local repo.
+pub fn strip_denied_diff(diff: &str, read_deny: &[String]) -> String {
+    if read_deny.is_empty() {
+        return diff.to_string();
+    }
+    let mut keep = true;
+    diff.split_inclusive('\n')
+        .filter(|line| {
+            if let Some(header) = line.strip_prefix("diff --git ") {
+                let header = header.trim_end().replace('"', "");
+                keep = !header
+                    .split_once(" b/")
+                    .map(|(old, new)| [old.trim_start_matches("a/"), new])
+                    .is_some_and(|paths| {
+                        paths
+                            .iter()
+                            .any(|path| read_file::is_denied(path, read_deny))
+                    });
+            }
+            keep
+        })
+        .collect()
+}
+
+/// Where a page of tool output starts and how many lines it may hold.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+    /// Byte within line `offset` to resume from, for a line too large to fit
+    /// in one page.
+    pub byte_offset: usize,
+}
+
+/// Read the optional `offset`/`limit`/`byte_offset` paging parameters from a
+/// tool input.
+pub fn page_params(input: &serde_json::Value, default_limit: usize) -> Page {
+    let param = |name: &str| input.get(name).and_then(|v| v.as_u64());
+    Page {
+        offset: param("offset").unwrap_or(0) as usize,
+        limit: param("limit").map_or(default_limit, |l| l.max(1) as usize),
+        byte_offset: param("byte_offset").unwrap_or(0) as usize,
+    }
+}
+
+/// Return up to `page.limit` lines of `output` starting at line
+/// `page.offset`, capped at `max_bytes`.
+///
+/// Output that fits is returned untouched. Otherwise a header gives the total
+/// size and where to continue, so the model can page through the rest on
+/// purpose instead of losing it. A line larger than `max_bytes` is paged by
+/// byte, so even a minified file can be read back whole.
+pub fn paginate(output: &str, page: Page, max_bytes: usize) -> String {
+    let Page {
+        offset,
+        limit,
+        byte_offset,
+    } = page;
+    let lines: Vec<&str> = output.lines().collect();
+    let total = lines.len();
+    if offset == 0 && byte_offset == 0 && total <= limit && output.len() <= max_bytes {
+        return output.to_string();
+    }
+    if offset >= total {
+        return format!("[offset {offset} is past the end; output has {total} lines]");
+    }
+
+    let first = lines[offset];
+    let mut start = byte_offset.min(first.len());
+    while !first.is_char_boundary(start) {
+        start -= 1;
+    }
+
+    let mut end = (offset + limit).min(total);
+    let mut body = String::new();
+    let mut cut = None;
+    for (i, line) in lines[offset..end].iter().enumerate() {
+        let line = if i == 0 { &line[start..] } else { line };
+        let needed = line.len() + usize::from(i > 0);
+        if body.len() + needed > max_bytes {
+            if i == 0 {
+                // A single line larger than the budget: return as much of it
+                // as fits and continue from there on the next page.
+                let mut len = max_bytes;
+                while !line.is_char_boundary(len) {
+                    len -= 1;
+                }
+                if len == 0 {
+                    len = line.chars().next().map_or(0, char::len_utf8);
+                }
+                body.push_str(&line[..len]);
+                cut = Some(start + len);
+                end = offset + 1;
+            } else {
+                end = offset + i;
+            }
+            break;
+        }
+        if i > 0 {
+            body.push('\n');
+        }
+        body.push_str(line);
+    }
+
+    let mut header = format!(
+        "[lines {}-{end} of {total}, {} bytes total",
+        offset + 1,
+        output.len()
+    );
+    match cut {
+        Some(cut) => header.push_str(&format!(
+            "; line {} bytes {start}-{cut} of {}; pass offset={offset} byte_offset={cut} to continue",
+            offset + 1,
+            first.len()
+        )),
+        None => {
+            if start > 0 {
+                header.push_str(&format!("; line {} from byte {start}", offset + 1));
+            }
+            if end < total {
+                header.push_str(&format!("; pass offset={end} to continue"));
+            }
+        }
+    }
+    header.push(']');
+    format!("{header}\n{body}")
+}
+
 pub async fn dispatch(
     name: &str,
     input: &serde_json::Value,
-    repo_root: &Path,
-    github: Option<&GitHubClient>,
+    ctx: &ToolContext<'_>,
 ) -> Result<String> {
+    let ToolContext {
+        repo_root,
+        forge,
+        config,
+        ..
+    } = *ctx;
+    let github = forge.and_then(ForgeClient::github);
+    let need_forge = |tool: &str| {
+        forge.ok_or_else(|| {
+            crate::error::Error::Tool(format!(
+                "{tool} requires GITHUB_TOKEN (GITLAB_TOKEN or GITEA_TOKEN on those forges) to be set"
+            ))
+        })
+    };
+    if !config.is_enabled(name) {
+        return Err(crate::error::Error::Tool(format!(
+            "{name} is disabled for this repository"
+        )));
+    }
     match name {
-        "read_file" => read_file::execute(repo_root, input),
+        "read_file" => read_file::execute(ctx, input),
         "list_files" => list_files::execute(repo_root, input),
-        "grep" => grep::execute(repo_root, input),
-        "git_show" => git_show::execute(repo_root, input),
+        "grep" => grep::execute(ctx, input),
+        "git_show" => git_show::execute(ctx, input),
         "get_commits" => get_commits::execute(repo_root, input),
-        "get_pr" => {
+        "diff_range" => diff_range::execute(ctx, input),
+        "dependency_changes" => dependency_changes::execute(ctx, input),
+        "get_changelog_entry" => get_changelog_entry::execute(ctx, input),
+        "get_pr" => get_pr::execute(need_forge(name)?, input).await,
+        "get_pr_comments" => {
             let gh = github.ok_or_else(|| {
-                crate::error::Error::Tool("get_pr requires GITHUB_TOKEN to be set".into())
+                crate::error::Error::Tool("get_pr_comments requires GITHUB_TOKEN to be set".into())
             })?;
-            get_pr::execute(gh, input).await
+            get_pr_comments::execute(gh, input).await
         }
         "get_pr_diff" => {
+            get_pr_diff::execute(need_forge(name)?, &ctx.config.read_deny, input).await
+        }
+        "get_pr_files" => {
+            let gh = github.ok_or_else(|| {
+                crate::error::Error::Tool("get_pr_files requires GITHUB_TOKEN to be set".into())
+            })?;
+            get_pr_files::execute(gh, input).await
+        }
+        "get_issue" => get_issue::execute(need_forge(name)?, input).await,
+        "search_github" => {
+            let gh = github.ok_or_else(|| {
+                crate::error::Error::Tool("search_github requires GITHUB_TOKEN to be set".into())
+            })?;
+            search_github::execute(gh, input).await
+        }
+        "get_release" => {
             let gh = github.ok_or_else(|| {
-                crate::error::Error::Tool("get_pr_diff requires GITHUB_TOKEN to be set".into())
+                crate::error::Error::Tool("get_release requires GITHUB_TOKEN to be set".into())
             })?;
-            get_pr_diff::execute(gh, input).await
+            get_release::execute(gh, input).await
         }
-        "get_issue" => {
+        "list_releases" => {
             let gh = github.ok_or_else(|| {
-                crate::error::Error::Tool("get_issue requires GITHUB_TOKEN to be set".into())
+                crate::error::Error::Tool("list_releases requires GITHUB_TOKEN to be set".into())
             })?;
-            get_issue::execute(gh, input).await
+            list_releases::execute(gh, input).await
         }
-        _ => Err(crate::error::Error::Tool(format!("unknown tool: {name}"))),
+        _ => match config.custom.iter().find(|t| t.name == name) {
+            Some(tool) => custom::execute(repo_root, tool, input)
+                .await
+                .map(|output| strip_denied_lines(&output, &config.read_deny)),
+            None => Err(crate::error::Error::Tool(format!("unknown tool: {name}"))),
+        },
     }
 }
 
@@ -104,54 +410,235 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_deny_pathspecs() {
+        assert_eq!(
+            deny_pathspecs(&["*.pem".into(), "secrets/".into(), "src/gen.rs".into()]),
+            [
+                ":(exclude,glob)**/*.pem",
+                ":(exclude,glob)**/*.pem/**",
+                ":(exclude,glob)**/secrets/**",
+                ":(exclude,glob)src/gen.rs",
+                ":(exclude,glob)src/gen.rs/**",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_denied_lines() {
+        let deny = vec!["secrets/**".to_string()];
+        assert_eq!(
+            strip_denied_lines("src/a.rs:1:x\nsecrets/key:2:y\nplain line", &deny),
+            "src/a.rs:1:x\nplain line"
+        );
+        assert_eq!(strip_denied_lines("a\n", &[]), "a\n");
+    }
+
     #[test]
     fn test_all_definitions_without_github() {
-        let defs = all_definitions(false, true, false);
-        assert_eq!(defs.len(), 6);
+        let defs = all_definitions(None, true, false, &ToolsConfig::default());
+        assert_eq!(defs.len(), 9);
         let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
         assert!(names.contains(&"read_file"));
         assert!(names.contains(&"list_files"));
         assert!(names.contains(&"grep"));
         assert!(names.contains(&"git_show"));
         assert!(names.contains(&"get_commits"));
+        assert!(names.contains(&"diff_range"));
+        assert!(names.contains(&"dependency_changes"));
+        assert!(names.contains(&"get_changelog_entry"));
         assert!(names.contains(&"submit_release_notes"));
     }
 
     #[test]
     fn test_all_definitions_with_github() {
-        let defs = all_definitions(true, true, true);
-        assert_eq!(defs.len(), 9);
+        let defs = all_definitions(Some(ForgeKind::GitHub), true, true, &ToolsConfig::default());
+        assert_eq!(defs.len(), 17);
         let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
         assert!(names.contains(&"get_pr"));
+        assert!(names.contains(&"get_pr_comments"));
+        assert!(names.contains(&"get_pr_files"));
         assert!(names.contains(&"get_pr_diff"));
         assert!(names.contains(&"get_issue"));
+        assert!(names.contains(&"search_github"));
+        assert!(names.contains(&"get_release"));
+        assert!(names.contains(&"list_releases"));
+    }
+
+    #[test]
+    fn test_all_definitions_with_gitlab() {
+        let defs = all_definitions(Some(ForgeKind::GitLab), true, true, &ToolsConfig::default());
+        let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(&names[9..], ["get_pr", "get_pr_diff", "get_issue"]);
+        let get_pr = defs.iter().find(|d| d.name == "get_pr").unwrap();
+        assert!(get_pr.description.contains("!123"));
+
+        let defs = all_definitions(Some(ForgeKind::Gitea), true, true, &ToolsConfig::default());
+        let get_pr = defs.iter().find(|d| d.name == "get_pr").unwrap();
+        assert!(get_pr.description.contains("Gitea pull request"));
+        assert!(!defs.iter().any(|d| d.name == "search_github"));
     }
 
     #[tokio::test]
     async fn test_dispatch_unknown_tool() {
         let tmp = std::env::temp_dir();
-        let err = dispatch("nonexistent_tool", &json!({}), &tmp, None)
-            .await
-            .unwrap_err();
+        let err = dispatch(
+            "nonexistent_tool",
+            &json!({}),
+            &ToolContext::new(&tmp, None),
+        )
+        .await
+        .unwrap_err();
         assert!(err.to_string().contains("unknown tool"));
     }
 
     #[tokio::test]
     async fn test_dispatch_get_pr_without_github() {
         let tmp = std::env::temp_dir();
-        let err = dispatch("get_pr", &json!({"number": 1}), &tmp, None)
-            .await
-            .unwrap_err();
+        let err = dispatch(
+            "get_pr",
+            &json!({"number": 1}),
+            &ToolContext::new(&tmp, None),
+        )
+        .await
+        .unwrap_err();
         assert!(err.to_string().contains("GITHUB_TOKEN"));
     }
 
     #[tokio::test]
     async fn test_dispatch_get_issue_without_github() {
         let tmp = std::env::temp_dir();
-        let err = dispatch("get_issue", &json!({"number": 1}), &tmp, None)
+        let err = dispatch(
+            "get_issue",
+            &json!({"number": 1}),
+            &ToolContext::new(&tmp, None),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("GITHUB_TOKEN"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_custom_tool() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("hello.txt", "hi");
+        repo.commit("init");
+
+        let config = ToolsConfig {
+            custom: vec![crate::config::CustomTool {
+                name: "tracked".into(),
+                description: "List tracked files".into(),
+                input_schema: json!({"type": "object"}),
+                command: vec!["git".into(), "ls-files".into()],
+                timeout: None,
+                max_output: None,
+            }],
+            ..ToolsConfig::default()
+        };
+        let defs = all_definitions(None, true, false, &config);
+        assert!(defs.iter().any(|d| d.name == "tracked"));
+
+        let ctx = ToolContext {
+            config: &config,
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = dispatch("tracked", &json!({}), &ctx).await.unwrap();
+        assert!(result.contains("hello.txt"));
+    }
+
+    #[test]
+    fn test_all_definitions_filters_by_policy() {
+        let config = ToolsConfig {
+            enabled: Some(vec!["read_file".into(), "get_pr".into()]),
+            disabled: vec!["get_pr".into()],
+            ..ToolsConfig::default()
+        };
+        let defs = all_definitions(Some(ForgeKind::GitHub), true, false, &config);
+        let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["read_file", "submit_release_notes"]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_disabled_tool() {
+        let repo = crate::test_helpers::TempRepo::new();
+        repo.write_file("a.txt", "a");
+        repo.commit("init");
+
+        let config = ToolsConfig {
+            disabled: vec!["read_file".into()],
+            ..ToolsConfig::default()
+        };
+        let ctx = ToolContext {
+            config: &config,
+            ..ToolContext::new(repo.path(), None)
+        };
+        let err = dispatch("read_file", &json!({"path": "a.txt"}), &ctx)
             .await
             .unwrap_err();
-        assert!(err.to_string().contains("GITHUB_TOKEN"));
+        assert!(err.to_string().contains("disabled"), "err: {err}");
+    }
+
+    fn lines(offset: usize, limit: usize) -> Page {
+        Page {
+            offset,
+            limit,
+            byte_offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_paginate() {
+        let output = "a\nb\nc\nd\ne";
+        assert_eq!(paginate(output, lines(0, 10), 100), output);
+        assert_eq!(
+            paginate(output, lines(0, 2), 100),
+            "[lines 1-2 of 5, 9 bytes total; pass offset=2 to continue]\na\nb"
+        );
+        assert_eq!(
+            paginate(output, lines(3, 10), 100),
+            "[lines 4-5 of 5, 9 bytes total]\nd\ne"
+        );
+        assert!(paginate(output, lines(9, 2), 100).contains("past the end"));
+    }
+
+    #[test]
+    fn test_paginate_byte_budget() {
+        let output = "aaaa\nbbbb\ncccc";
+        // Stops at a line boundary so the continuation offset is exact.
+        assert_eq!(
+            paginate(output, lines(0, 10), 9),
+            "[lines 1-2 of 3, 14 bytes total; pass offset=2 to continue]\naaaa\nbbbb"
+        );
+        // A single oversized line is cut rather than dropped.
+        let long = "é".repeat(10);
+        let page = paginate(&long, lines(0, 10), 5);
+        assert!(
+            page.contains("line 1 bytes 0-4 of 20; pass offset=0 byte_offset=4"),
+            "page: {page}"
+        );
+        assert!(page.ends_with("\néé"));
+    }
+
+    #[test]
+    fn test_paginate_reads_back_an_oversized_line() {
+        let long = format!("{}\nnext", "0123456789".repeat(3));
+        let mut page = lines(0, 10);
+        let mut line = String::new();
+        loop {
+            let out = paginate(&long, page, 12);
+            let (header, body) = out.split_once('\n').unwrap();
+            let Some((_, rest)) = header.split_once("byte_offset=") else {
+                assert_eq!(
+                    header,
+                    "[lines 1-2 of 2, 35 bytes total; line 1 from byte 24]"
+                );
+                line.push_str(body);
+                break;
+            };
+            line.push_str(body);
+            page.byte_offset = rest.split_once(' ').unwrap().0.parse().unwrap();
+        }
+        assert_eq!(line, long);
     }
 
     #[test]
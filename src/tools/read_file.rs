@@ -1,16 +1,22 @@
 use std::path::Path;
 use std::process::Command;
 
+use regex::Regex;
 use serde_json::json;
 
+use super::ToolContext;
 use crate::error::{Error, Result};
+use crate::git;
 use crate::llm::ToolDefinition;
 
+const DEFAULT_LIMIT: usize = 2000;
+const MAX_BYTES: usize = 100_000;
+
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
         name: "read_file".into(),
         description:
-            "Read the contents of a git-tracked file in the repository. Path is relative to the repo root."
+            "Read the contents of a git-tracked file in the repository as of the release being documented (or another ref). Path is relative to the repo root."
                 .into(),
         input_schema: json!({
             "type": "object",
@@ -18,6 +24,22 @@ pub fn definition() -> ToolDefinition {
                 "path": {
                     "type": "string",
                     "description": "File path relative to repo root (must be tracked by git)"
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Git ref (tag, branch, or SHA) to read the file at. Defaults to the release tag."
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Line to start from (0-based), for paging through large files. Defaults to 0."
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of lines to return. Defaults to 2000."
+                },
+                "byte_offset": {
+                    "type": "integer",
+                    "description": "Byte to resume from within the line at offset, when a single line is too long for one page. Defaults to 0."
                 }
             },
             "required": ["path"]
@@ -25,11 +47,77 @@ pub fn definition() -> ToolDefinition {
     }
 }
 
-pub fn execute(repo_root: &Path, input: &serde_json::Value) -> Result<String> {
+pub fn execute(ctx: &ToolContext<'_>, input: &serde_json::Value) -> Result<String> {
     let rel_path = input["path"]
         .as_str()
         .ok_or_else(|| Error::Tool("read_file: missing 'path' parameter".into()))?;
+    let read_deny = &ctx.config.read_deny;
+
+    if is_denied(rel_path, read_deny) {
+        return Err(Error::Tool(format!(
+            "read_file: {rel_path}: denied by tools.read_deny"
+        )));
+    }
+
+    // Read as of the release by default, so the model doesn't quote code from
+    // a newer checkout. An untagged target (HEAD, or a version not tagged yet)
+    // falls back to the working tree.
+    let git_ref = match input.get("ref").and_then(|v| v.as_str()) {
+        Some(r) => Some(r),
+        None => ctx
+            .tag
+            .filter(|t| *t != "HEAD" && git::ref_exists(ctx.repo_root, t)),
+    };
+
+    let contents = match git_ref {
+        Some(git_ref) => read_at_ref(ctx.repo_root, git_ref, rel_path)?,
+        None => read_worktree(ctx.repo_root, read_deny, rel_path)?,
+    };
+
+    let page = super::page_params(input, DEFAULT_LIMIT);
+    Ok(super::paginate(&contents, page, MAX_BYTES))
+}
+
+fn read_at_ref(repo_root: &Path, git_ref: &str, rel_path: &str) -> Result<String> {
+    if git_ref.starts_with('-') {
+        return Err(Error::Tool(format!("read_file: invalid ref: {git_ref}")));
+    }
+    let rel_path = rel_path.trim_start_matches("./");
+    if rel_path.split('/').any(|c| c == "..") {
+        return Err(Error::Tool(format!(
+            "read_file: {rel_path}: not a git-tracked file at {git_ref}"
+        )));
+    }
+    let spec = format!("{git_ref}:{rel_path}");
+
+    // Sandbox: the path must name a file in the commit's tree, which keeps
+    // the tracked-files-only guarantee without touching the working tree.
+    let kind = Command::new("git")
+        .args(["cat-file", "-t", &spec])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| Error::Tool(format!("read_file: git cat-file: {e}")))?;
+    if !kind.status.success() || String::from_utf8_lossy(&kind.stdout).trim() != "blob" {
+        return Err(Error::Tool(format!(
+            "read_file: {rel_path}: not a git-tracked file at {git_ref}"
+        )));
+    }
 
+    let output = Command::new("git")
+        .args(["show", &spec])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| Error::Tool(format!("read_file: git show: {e}")))?;
+    if !output.status.success() {
+        return Err(Error::Tool(format!(
+            "read_file: {spec}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn read_worktree(repo_root: &Path, read_deny: &[String], rel_path: &str) -> Result<String> {
     // Sandbox: only permit reading files tracked by git. This excludes secrets
     // like .env, gitignored build artifacts, and .git internals.
     let tracked = Command::new("git")
@@ -58,18 +146,64 @@ pub fn execute(repo_root: &Path, input: &serde_json::Value) -> Result<String> {
         )));
     }
 
-    let contents = xx::file::read_to_string(&canonical)
-        .map_err(|e| Error::Tool(format!("read_file: {rel_path}: {e}")))?;
+    // Check the resolved path too, so `a/../secrets/x` or a symlink into a
+    // denied directory can't slip past the globs.
+    let resolved = canonical
+        .strip_prefix(&root_canonical)
+        .unwrap_or(&canonical)
+        .to_string_lossy();
+    if is_denied(&resolved, read_deny) {
+        return Err(Error::Tool(format!(
+            "read_file: {rel_path}: denied by tools.read_deny"
+        )));
+    }
 
-    // Truncate very large files
-    if contents.len() > 100_000 {
-        Ok(format!(
-            "{}...\n\n[file truncated at 100KB]",
-            &contents[..100_000]
-        ))
-    } else {
-        Ok(contents)
+    xx::file::read_to_string(&canonical)
+        .map_err(|e| Error::Tool(format!("read_file: {rel_path}: {e}")))
+}
+
+/// Whether `path` matches any of the `read_deny` globs.
+///
+/// `*` and `?` stay within one path segment and `**` spans segments. A glob
+/// without a `/` matches at any depth (like `.gitignore`), and a glob ending in
+/// `/` matches everything below that directory.
+pub fn is_denied(path: &str, patterns: &[String]) -> bool {
+    let path = path.trim_start_matches("./").replace('\\', "/");
+    patterns
+        .iter()
+        .any(|pattern| glob_regex(pattern).is_some_and(|re| re.is_match(&path)))
+}
+
+fn glob_regex(pattern: &str) -> Option<Regex> {
+    let pattern = pattern.trim_start_matches("./");
+    let (pattern, dir_only) = match pattern.strip_suffix('/') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let mut re = String::from("^");
+    if !pattern.contains('/') {
+        re.push_str("(?:.*/)?");
+    }
+    let pattern = pattern.trim_start_matches('/');
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
     }
+    re.push_str(if dir_only { "/.*$" } else { "(?:/.*)?$" });
+    Regex::new(&re).ok()
 }
 
 #[cfg(test)]
@@ -84,7 +218,11 @@ mod tests {
         repo.write_file("hello.txt", "world");
         repo.commit("init");
 
-        let result = execute(repo.path(), &json!({"path": "hello.txt"})).unwrap();
+        let result = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"path": "hello.txt"}),
+        )
+        .unwrap();
         assert_eq!(result, "world");
     }
 
@@ -96,7 +234,11 @@ mod tests {
         // .env exists on disk but is not tracked
         repo.write_file(".env", "SECRET=hunter2");
 
-        let err = execute(repo.path(), &json!({"path": ".env"})).unwrap_err();
+        let err = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"path": ".env"}),
+        )
+        .unwrap_err();
         assert!(
             err.to_string().contains("not a git-tracked file"),
             "unexpected error: {err}"
@@ -110,7 +252,11 @@ mod tests {
         repo.commit("init");
         repo.write_file("secrets.txt", "API_KEY=abc");
 
-        let err = execute(repo.path(), &json!({"path": "secrets.txt"})).unwrap_err();
+        let err = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"path": "secrets.txt"}),
+        )
+        .unwrap_err();
         assert!(
             err.to_string().contains("not a git-tracked file"),
             "unexpected error: {err}"
@@ -123,7 +269,11 @@ mod tests {
         repo.write_file("README.md", "# hi");
         repo.commit("init");
 
-        let err = execute(repo.path(), &json!({"path": "../secret.txt"})).unwrap_err();
+        let err = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"path": "../secret.txt"}),
+        )
+        .unwrap_err();
         assert!(
             err.to_string().contains("not a git-tracked file"),
             "unexpected error: {err}"
@@ -145,7 +295,11 @@ mod tests {
         symlink(&secret, repo.path().join("link")).unwrap();
         repo.commit("add symlink");
 
-        let err = execute(repo.path(), &json!({"path": "link"})).unwrap_err();
+        let err = execute(
+            &ToolContext::new(repo.path(), None),
+            &json!({"path": "link"}),
+        )
+        .unwrap_err();
         assert!(
             err.to_string().contains("escapes repo root"),
             "unexpected error: {err}"
@@ -158,15 +312,156 @@ mod tests {
         repo.write_file("big.txt", &"x".repeat(200_000));
         repo.commit("init");
 
-        let result = execute(repo.path(), &json!({"path": "big.txt"})).unwrap();
-        assert!(result.contains("[file truncated at 100KB]"));
+        let ctx = ToolContext::new(repo.path(), None);
+        let result = execute(&ctx, &json!({"path": "big.txt"})).unwrap();
+        assert!(result.starts_with(
+            "[lines 1-1 of 1, 200000 bytes total; line 1 bytes 0-100000 of 200000; pass offset=0 byte_offset=100000 to continue]"
+        ));
+        assert!(result.len() < 200_000);
+
+        let rest = execute(&ctx, &json!({"path": "big.txt", "byte_offset": 100_000})).unwrap();
+        let (header, body) = rest.split_once('\n').unwrap();
+        assert_eq!(
+            header,
+            "[lines 1-1 of 1, 200000 bytes total; line 1 from byte 100000]"
+        );
+        assert_eq!(body.len(), 100_000);
+    }
+
+    #[test]
+    fn test_read_file_paging() {
+        let repo = TempRepo::new();
+        let contents: String = (1..=5000).map(|i| format!("line {i}\n")).collect();
+        repo.write_file("long.txt", &contents);
+        repo.commit("init");
+
+        let ctx = ToolContext::new(repo.path(), None);
+        let first = execute(&ctx, &json!({"path": "long.txt"})).unwrap();
+        assert!(first.starts_with("[lines 1-2000 of 5000"));
+        assert!(first.contains("pass offset=2000 to continue"));
+        assert!(first.ends_with("line 2000"));
+
+        let page = execute(
+            &ctx,
+            &json!({"path": "long.txt", "offset": 4990, "limit": 20}),
+        )
+        .unwrap();
+        assert!(page.starts_with("[lines 4991-5000 of 5000"));
+        assert!(page.ends_with("line 5000"));
+    }
+
+    #[test]
+    fn test_read_file_denied() {
+        let repo = TempRepo::new();
+        repo.write_file("secrets/key.txt", "hunter2");
+        repo.write_file("certs/server.pem", "pem");
+        repo.write_file("README.md", "# hi");
+        repo.commit("init");
+
+        let config = crate::config::ToolsConfig {
+            read_deny: vec!["secrets/**".into(), "*.pem".into()],
+            ..Default::default()
+        };
+        let ctx = ToolContext {
+            config: &config,
+            ..ToolContext::new(repo.path(), None)
+        };
+        for path in [
+            "secrets/key.txt",
+            "certs/server.pem",
+            "./secrets/../secrets/key.txt",
+        ] {
+            let err = execute(&ctx, &json!({"path": path})).unwrap_err();
+            assert!(err.to_string().contains("read_deny"), "{path}: {err}");
+        }
+        let err = execute(&ctx, &json!({"path": "secrets/key.txt", "ref": "HEAD"})).unwrap_err();
+        assert!(err.to_string().contains("read_deny"), "{err}");
+        let result = execute(&ctx, &json!({"path": "README.md"})).unwrap();
+        assert_eq!(result, "# hi");
+    }
+
+    #[test]
+    fn test_is_denied_globs() {
+        let deny = |p: &str| vec![p.to_string()];
+        assert!(is_denied("a/b/c.env", &deny("*.env")));
+        assert!(is_denied("internal/x.rs", &deny("internal/")));
+        assert!(is_denied("internal", &deny("internal")));
+        assert!(!is_denied("src/internal_api.rs", &deny("internal")));
+        assert!(is_denied("src/a/b/gen.rs", &deny("src/**/gen.rs")));
+        assert!(is_denied("src/gen.rs", &deny("src/**/gen.rs")));
+        assert!(!is_denied("src/a/gen.rs", &deny("src/*.rs")));
+    }
+
+    #[test]
+    fn test_read_file_defaults_to_release_tag() {
+        let repo = TempRepo::new();
+        repo.write_file("lib.rs", "fn old() {}");
+        repo.commit("first");
+        repo.tag("v1.0.0");
+        repo.write_file("lib.rs", "fn new() {}");
+        repo.write_file("added.rs", "fn later() {}");
+        repo.commit("second");
+
+        let ctx = ToolContext {
+            tag: Some("v1.0.0"),
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({"path": "lib.rs"})).unwrap();
+        assert_eq!(result, "fn old() {}");
+
+        let err = execute(&ctx, &json!({"path": "added.rs"})).unwrap_err();
+        assert!(err.to_string().contains("not a git-tracked file at v1.0.0"));
+
+        let result = execute(&ctx, &json!({"path": "lib.rs", "ref": "HEAD"})).unwrap();
+        assert_eq!(result, "fn new() {}");
+    }
+
+    #[test]
+    fn test_read_file_untagged_release_uses_worktree() {
+        let repo = TempRepo::new();
+        repo.write_file("lib.rs", "fn current() {}");
+        repo.commit("first");
+
+        let ctx = ToolContext {
+            tag: Some("v9.9.9"),
+            ..ToolContext::new(repo.path(), None)
+        };
+        let result = execute(&ctx, &json!({"path": "lib.rs"})).unwrap();
+        assert_eq!(result, "fn current() {}");
+    }
+
+    #[test]
+    fn test_read_file_at_ref_rejects_directories_and_traversal() {
+        let repo = TempRepo::new();
+        repo.write_file("src/main.rs", "fn main() {}");
+        repo.commit("init");
+
+        let ctx = ToolContext::new(repo.path(), None);
+        for path in ["src", "../outside.txt", "src/../../outside.txt"] {
+            let err = execute(&ctx, &json!({"path": path, "ref": "HEAD"})).unwrap_err();
+            assert!(
+                err.to_string().contains("not a git-tracked file"),
+                "{path}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_file_at_ref_truncation() {
+        let repo = TempRepo::new();
+        repo.write_file("big.txt", &"x".repeat(200_000));
+        repo.commit("init");
+
+        let ctx = ToolContext::new(repo.path(), None);
+        let result = execute(&ctx, &json!({"path": "big.txt", "ref": "HEAD"})).unwrap();
+        assert!(result.contains("line 1 bytes 0-100000 of 200000"));
         assert!(result.len() < 200_000);
     }
 
     #[test]
     fn test_read_file_missing_path() {
         let repo = TempRepo::new();
-        let err = execute(repo.path(), &json!({})).unwrap_err();
+        let err = execute(&ToolContext::new(repo.path(), None), &json!({})).unwrap_err();
         assert!(err.to_string().contains("missing 'path'"));
     }
 }
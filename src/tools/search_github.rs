@@ -0,0 +1,141 @@
+use serde_json::json;
+
+use crate::error::{Error, Result};
+use crate::github::GitHubClient;
+use crate::llm::ToolDefinition;
+
+const PER_PAGE: u8 = 20;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "search_github".into(),
+        description: "Search this repository's GitHub issues and pull requests. Supports GitHub search qualifiers such as is:issue, is:pr, is:open, is:closed, label:bug, author:name, closed:2024-01-01..2024-02-01, in:title. Useful for linking fixes to the bug reports they resolve.".into(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Search terms and qualifiers (e.g. '\"--dry-run\" is:issue', 'label:regression is:closed'). Results are always limited to this repository."
+                },
+                "page": {
+                    "type": "integer",
+                    "description": "Page of results, starting at 1. Defaults to 1."
+                }
+            },
+            "required": ["query"]
+        }),
+    }
+}
+
+pub async fn execute(github: &GitHubClient, input: &serde_json::Value) -> Result<String> {
+    let query = input["query"]
+        .as_str()
+        .filter(|q| !q.trim().is_empty())
+        .ok_or_else(|| Error::Tool("search_github: missing 'query' parameter".into()))?;
+    // Negation, grouping and quotes don't stop GitHub reading a qualifier.
+    if let Some(scope) = query.split_whitespace().find(|t| {
+        let t = t.trim_start_matches(['-', '(', '"', '\'']).to_lowercase();
+        ["repo:", "org:", "user:"].iter().any(|p| t.starts_with(p))
+    }) {
+        return Err(Error::Tool(format!(
+            "search_github: '{scope}' is not allowed; searches are limited to this repository"
+        )));
+    }
+    let page = input
+        .get("page")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1)
+        .max(1) as u32;
+
+    let results = github.search_issues(query, page, PER_PAGE).await?;
+    if results.items.is_empty() {
+        return Ok(format!("No issues or PRs match {query:?}."));
+    }
+
+    let shown = (page as u64 - 1) * PER_PAGE as u64 + results.items.len() as u64;
+    let mut out = format!(
+        "{} results for {query:?} (showing {}-{shown})\n",
+        results.total_count,
+        shown - results.items.len() as u64 + 1
+    );
+    for item in &results.items {
+        let kind = if item.pull_request.is_some() {
+            "PR"
+        } else {
+            "issue"
+        };
+        let state = match item.closed_at.as_deref() {
+            Some(closed) => format!("{} {}", item.state, closed.get(..10).unwrap_or(closed)),
+            None => item.state.clone(),
+        };
+        out.push_str(&format!(
+            "\n#{} [{kind}, {state}] {} (@{})",
+            item.number, item.title, item.user.login
+        ));
+        if !item.labels.is_empty() {
+            let labels: Vec<&str> = item.labels.iter().map(|l| l.name.as_str()).collect();
+            out.push_str(&format!(" [{}]", labels.join(", ")));
+        }
+    }
+    if shown < results.total_count {
+        out.push_str(&format!(
+            "\n\n[more results available; pass page={} to continue]",
+            page + 1
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_search_github_compact_results() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 25,
+                "items": [
+                    {"number": 7, "title": "Crash on empty config", "state": "closed", "user": {"login": "alice"}, "labels": [{"name": "bug"}, {"name": "regression"}], "closed_at": "2024-03-01T12:00:00Z"},
+                    {"number": 9, "title": "Handle empty config", "state": "open", "user": {"login": "bob"}, "labels": [], "closed_at": null, "pull_request": {"url": "x"}}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", server.uri()).unwrap();
+
+        let result = execute(&gh, &json!({"query": "empty config"}))
+            .await
+            .unwrap();
+        assert!(result.starts_with("25 results for \"empty config\" (showing 1-2)"));
+        assert!(result.contains(
+            "#7 [issue, closed 2024-03-01] Crash on empty config (@alice) [bug, regression]"
+        ));
+        assert!(result.contains("#9 [PR, open] Handle empty config (@bob)\n"));
+        assert!(result.ends_with("pass page=2 to continue]"));
+    }
+
+    #[tokio::test]
+    async fn test_search_github_rejects_other_scopes() {
+        let gh = GitHubClient::with_base_url("token".into(), "owner/repo", "http://unused".into())
+            .unwrap();
+        let err = execute(&gh, &json!({"query": "bug repo:other/repo"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'repo:other/repo' is not allowed"));
+
+        for query in [
+            "bug -repo:owner/repo",
+            "bug (repo:other/repo OR label:x)",
+            "bug \"repo:other/repo\"",
+            "bug -(ORG:other",
+        ] {
+            let err = execute(&gh, &json!({"query": query})).await.unwrap_err();
+            assert!(err.to_string().contains("is not allowed"), "{query}");
+        }
+    }
+}